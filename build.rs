@@ -0,0 +1,57 @@
+/// Generates the solution registry. Every solution binary in `src/bin` is also
+/// compiled into the library as a module, so that all puzzles can be called
+/// in-process. Binaries are named after their puzzle, e.g. `2023-01.rs`, and
+/// grouped into a module per year. See `src/solutions.rs`.
+///
+/// The registry is only generated with the `in_process` feature, which
+/// `cargo all --in-process` enables.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    if env::var_os("CARGO_FEATURE_IN_PROCESS").is_none() {
+        return;
+    }
+
+    let mut puzzles: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let (year, day) = path.file_stem()?.to_str()?.split_once('-')?;
+            let (year, day): (u16, u8) = (year.parse().ok()?, day.parse().ok()?);
+            (year >= 2015 && (1..=25).contains(&day))
+                .then(|| (year, day, path.display().to_string()))
+        })
+        .collect();
+    puzzles.sort_unstable();
+
+    let mut registry = String::new();
+    let mut years: Vec<u16> = puzzles.iter().map(|(year, _, _)| *year).collect();
+    years.dedup();
+
+    for year in &years {
+        registry.push_str(&format!("pub mod y{year} {{\n"));
+        for (_, day, path) in puzzles.iter().filter(|(y, _, _)| y == year) {
+            registry.push_str(&format!(
+                "    #[path = {path:?}]\n    pub mod day{day:02};\n"
+            ));
+        }
+        registry.push_str("}\n\n");
+    }
+
+    registry.push_str("/// The solutions of all puzzles, ordered by year and day.\n");
+    registry.push_str("pub static SOLUTIONS: &[&dyn crate::template::solution::Solution] = &[\n");
+    for (year, day, _) in &puzzles {
+        registry.push_str(&format!("    &y{year}::day{day:02}::DaySolution,\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
@@ -1,7 +1,7 @@
 use regex::Regex;
 use std::error::Error;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 pub fn part_one(input: &str) -> Option<u32> {
     input.lines().map(get_2_digits).sum::<Result<u32, _>>().ok()
@@ -146,20 +146,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(142));
     }
 
     #[test]
     fn test_part_two() {
         // Only digits
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(142));
 
         // Digits and numbers
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
         assert_eq!(result, Some(281));
     }
 }
@@ -1,7 +1,14 @@
-use advent_of_code::puzzles::config::SETTINGS;
 use std::{error::Error, str::FromStr};
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(
+    2023,
+    2,
+    params {
+        red: u8 = 12,
+        green: u8 = 13,
+        blue: u8 = 14,
+    }
+);
 
 #[derive(Debug)]
 struct CubeSet {
@@ -92,12 +99,11 @@ impl Game {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let bag_contents_ = SETTINGS.day02.bag_contents;
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let bag_contents = CubeSet {
-        red: bag_contents_[0],
-        green: bag_contents_[1],
-        blue: bag_contents_[2],
+        red: params.red,
+        green: params.green,
+        blue: params.blue,
     };
 
     let mut n_possible = 0;
@@ -111,7 +117,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(n_possible)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, _params: &Params) -> Option<u32> {
     let games = parse_games(input).ok()?;
     Some(games.iter().map(|g| g.min_power()).sum())
 }
@@ -136,13 +142,24 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE).unwrap();
+        let result = part_one(&input, &Params::default());
         assert_eq!(result, Some(8));
+
+        let params = Params {
+            red: 20,
+            green: 20,
+            blue: 20,
+        };
+        assert_eq!(part_one(&input, &params), Some(15));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+            &Params::default(),
+        );
         assert_eq!(result, Some(2286));
     }
 }
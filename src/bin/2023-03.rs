@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d03::{Number, Schematic};
+use advent_of_code::puzzles::y2023::d03::{Number, Schematic};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2023, 3);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let schematic = Schematic::new(input);
@@ -28,13 +28,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(467835));
     }
 }
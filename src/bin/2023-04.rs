@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d04::CardGame;
+use advent_of_code::puzzles::y2023::d04::CardGame;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut total_points = 0;
@@ -37,13 +37,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(30));
     }
 }
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d05::Almanac;
+use advent_of_code::puzzles::y2023::d05::Almanac;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let almanac: Almanac = input.parse().ok()?;
@@ -40,13 +40,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(46));
     }
 }
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d06::{BoatRace, BoatTable};
+use advent_of_code::puzzles::y2023::d06::{BoatRace, BoatTable};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let table: BoatTable = input.parse().ok()?;
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(71503));
     }
 }
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d07::{Card1, Card2, HandsList};
+use advent_of_code::puzzles::y2023::d07::{Card1, Card2, HandsList};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let hands_list: HandsList<Card1> = input.parse().unwrap();
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(5905));
     }
 }
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d08::Network;
+use advent_of_code::puzzles::y2023::d08::Network;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let network: Network = input.parse().unwrap();
@@ -18,22 +18,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap());
         assert_eq!(result, Some(2));
 
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result =
+            part_one(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap());
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
+        let result =
+            part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 3).unwrap());
         assert_eq!(result, Some(6));
     }
 }
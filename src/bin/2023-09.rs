@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d09::Report;
+use advent_of_code::puzzles::y2023::d09::Report;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(2023, 9);
 
 pub fn part_one(input: &str) -> Option<i32> {
     let report: Report = input.parse().unwrap();
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(2));
     }
 }
@@ -0,0 +1,44 @@
+use advent_of_code::puzzles::y2023::d11::Galaxies;
+
+advent_of_code::solution!(
+    2023,
+    11,
+    params {
+        multiplier: u32 = 1_000_000,
+    }
+);
+
+pub fn part_one(input: &str, _params: &Params) -> Option<i64> {
+    let mut galaxies: Galaxies = input.parse().unwrap();
+    galaxies.expand(2);
+    Some(galaxies.sum_pairwise_dist())
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<i64> {
+    let mut galaxies: Galaxies = input.parse().unwrap();
+    galaxies.expand(params.multiplier);
+    Some(galaxies.sum_pairwise_dist())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+            &Params::default(),
+        );
+        assert_eq!(result, Some(374));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE).unwrap();
+        let result = part_two(&input, &Params { multiplier: 10 });
+        assert_eq!(result, Some(1030));
+        let result = part_two(&input, &Params { multiplier: 100 });
+        assert_eq!(result, Some(8410));
+    }
+}
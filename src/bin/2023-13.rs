@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d13::Valley;
+use advent_of_code::puzzles::y2023::d13::Valley;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2023, 13);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let valley: Valley = input.parse().unwrap();
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(405));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(400));
     }
 }
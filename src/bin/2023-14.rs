@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d14::Platform;
+use advent_of_code::puzzles::y2023::d14::Platform;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2023, 14);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut platform: Platform = input.parse().unwrap();
@@ -20,13 +20,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(64));
     }
 }
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d15::InitSequence;
+use advent_of_code::puzzles::y2023::d15::InitSequence;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let sequence: InitSequence = input.parse().unwrap();
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(1320));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(145));
     }
 }
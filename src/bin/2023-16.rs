@@ -1,10 +1,15 @@
-use advent_of_code::puzzles::d16::{Direction, MirrorGrid};
+use advent_of_code::puzzles::geom::{Direction, Point};
+use advent_of_code::puzzles::y2023::d16::MirrorGrid;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2023, 16);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mirrors: MirrorGrid = input.parse().unwrap();
-    Some(mirrors.follow_beam((0, 0), Direction::East).num_energized())
+    Some(
+        mirrors
+            .follow_beam(Point::new(0, 0), Direction::East)
+            .num_energized(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -18,13 +23,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(51));
     }
 }
@@ -0,0 +1,41 @@
+use advent_of_code::puzzles::y2023::d17::Map;
+
+advent_of_code::solution!(2023, 17, parse);
+
+pub fn parse(input: &str) -> Map {
+    input.parse().unwrap()
+}
+
+pub fn part_one(map: &Map) -> Option<u32> {
+    map.cheapest_path_cost_normal()
+}
+
+pub fn part_two(map: &Map) -> Option<u32> {
+    map.cheapest_path_cost_ultra()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(
+            &advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap(),
+        ));
+        assert_eq!(result, Some(102));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(
+            &advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap(),
+        ));
+        assert_eq!(result, Some(94));
+
+        let result = part_two(&parse(
+            &advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap(),
+        ));
+        assert_eq!(result, Some(71));
+    }
+}
@@ -0,0 +1,53 @@
+use advent_of_code::puzzles::y2023::d20::ModuleNetwork;
+
+advent_of_code::solution!(
+    2023,
+    20,
+    parse,
+    params {
+        presses: usize = 1000,
+    }
+);
+
+pub fn parse(input: &str) -> ModuleNetwork {
+    input.parse().unwrap()
+}
+
+pub fn part_one(network: &ModuleNetwork, params: &Params) -> Option<u32> {
+    let mut network = network.clone();
+    let (n_low, n_high) = network.press_multiple_and_count_pulses(params.presses);
+
+    Some(n_low * n_high)
+}
+
+pub fn part_two(network: &ModuleNetwork, _params: &Params) -> Option<u64> {
+    Some(network.steps_until_rx_first_low())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(
+            &parse(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap()),
+            &Params::default(),
+        );
+        assert_eq!(result, Some(32_000_000));
+
+        let result = part_one(
+            &parse(&advent_of_code::template::read_file_part("examples", PUZZLE, 2).unwrap()),
+            &Params::default(),
+        );
+        assert_eq!(result, Some(11687500));
+
+        let result = part_one(
+            &parse(&advent_of_code::template::read_file_part("examples", PUZZLE, 1).unwrap()),
+            &Params { presses: 1 },
+        );
+        assert_eq!(result, Some(32));
+    }
+
+    // No test case for part 2
+}
@@ -0,0 +1,40 @@
+use advent_of_code::puzzles::y2023::d21::Garden;
+
+advent_of_code::solution!(
+    2023,
+    21,
+    params {
+        steps: u64 = 64,
+        extrapolated_steps: u64 = 26501365,
+    }
+);
+
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
+    let garden: Garden = input.parse().unwrap();
+    Some(garden.num_tiles_reacheable_after(params.steps, false))
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
+    let garden: Garden = input.parse().unwrap();
+    Some(garden.num_tiles_reacheable_extrapolated(params.extrapolated_steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let params = Params {
+            steps: 6,
+            ..Params::default()
+        };
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+            &params,
+        );
+        assert_eq!(result, Some(16));
+    }
+
+    // More testcases can be found in module d21
+}
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d22::BrickPile;
+use advent_of_code::puzzles::y2023::d22::BrickPile;
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2023, 22);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let brick_pile: BrickPile = input.parse().unwrap();
@@ -18,13 +18,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(7));
     }
 }
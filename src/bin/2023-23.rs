@@ -0,0 +1,36 @@
+use advent_of_code::puzzles::y2023::d23::Map;
+
+advent_of_code::solution!(2023, 23, parse);
+
+pub fn parse(input: &str) -> Map {
+    input.parse().unwrap()
+}
+
+pub fn part_one(map: &Map) -> Option<u32> {
+    Some(map.longest_path_len_directed())
+}
+
+pub fn part_two(map: &Map) -> Option<u32> {
+    Some(map.longest_path_len_undirected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+        ));
+        assert_eq!(result, Some(94));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+        ));
+        assert_eq!(result, Some(154));
+    }
+}
@@ -0,0 +1,46 @@
+use advent_of_code::puzzles::y2023::d24::Hail;
+
+advent_of_code::solution!(
+    2023,
+    24,
+    params {
+        min: i64 = 200_000_000_000_000,
+        max: i64 = 400_000_000_000_000,
+    }
+);
+
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    let hail: Hail = input.parse().unwrap();
+    let range = params.min..=params.max;
+    Some(hail.count_intersections_within_xy(&range, &range))
+}
+
+pub fn part_two(input: &str, _params: &Params) -> Option<i64> {
+    let hail: Hail = input.parse().unwrap();
+    let (pos, _velocity) = hail.find_perfect_throw_velocity_and_position();
+    Some(pos.x + pos.y + pos.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let params = Params { min: 7, max: 27 };
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+            &params,
+        );
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", PUZZLE).unwrap(),
+            &Params::default(),
+        );
+        assert_eq!(result, Some(47));
+    }
+}
@@ -1,6 +1,6 @@
-use advent_of_code::puzzles::d25::Wiring;
+use advent_of_code::puzzles::y2023::d25::Wiring;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2023, 25);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let wiring: Wiring = input.parse().unwrap();
@@ -18,7 +18,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE).unwrap());
         assert_eq!(result, Some(54));
     }
 }
@@ -1,5 +1,13 @@
+// Allows the solutions in `src/bin` to be compiled into the library as well, see
+// the `in_process` feature.
+extern crate self as advent_of_code;
+
 mod day;
 pub mod puzzles;
+#[cfg(feature = "in_process")]
+pub mod solutions;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use year::*;
@@ -1,55 +1,161 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::{
+    commands::{
+        all, download, extract, progress, read, scaffold,
+        solve::{self, SolveArgs},
+    },
+    config,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{env, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            baselines::BaselineArgs,
+            parse_duration,
+            report::OutputFormat,
+            runner::{BenchSettings, RunSettings},
+        },
+        PuzzleId, Year,
+    };
+
+    const HELP: &str = "\
+Usage: cargo <command> [options]
+
+Every command takes `--year <year>` to pick the event, which defaults to the
+AOC_YEAR environment variable (set to 2023 in `.cargo/config.toml`).
+
+Commands:
+  scaffold <day>       Create the solution, puzzle module, input and example files of
+                       a day, from the templates in `templates/` if present. If the
+                       day was downloaded, its examples and answers are filled in.
+  download <day>       Download the input and puzzle description of a day.
+  extract <day>        Extract the examples and their answers from a downloaded day.
+  read <day>           Read the puzzle description of a day in the terminal.
+  solve <day>          Run the solution of a day.
+    --release            Run an optimized build.
+    --time               Bench the solution after a few warmup runs.
+    --bench-time <time>  Time budget of a benchmark, e.g. `5s` (default: `1s`).
+    --samples <n>        Take exactly <n> samples, ignoring the time budget.
+    --check              Compare the results to the recorded answers.
+    --mem                Count the allocations of every part.
+    --profile            Print the durations and calls of the `span!`s of every part.
+    --timeout <time>     Stop the solution after the given time, e.g. `30s`.
+    --format <format>    Print the results as `text` (default) or `json`.
+    --submit <part>      Submit the result of a part.
+    --input <path>       Read the input from a file instead, or from stdin with `-`.
+    --param <name=value> Override a parameter of the solution. Can be repeated.
+    --watch              Rerun the solution whenever its sources or data files change.
+  all                  Run the solutions of all days of the year.
+    --release            Run optimized builds.
+    --time               Bench the solutions. With --release, update the README.
+    --bench-time <time>  Time budget of a benchmark, e.g. `5s` (default: `1s`).
+    --samples <n>        Take exactly <n> samples, ignoring the time budget.
+    --check              Compare the results to the recorded answers.
+    --mem                Count the allocations of every part and print a table.
+    --profile            Record the `span!`s of every part and print them per day,
+                         and in total over all days.
+    --timeout <time>     Stop a day after the given time, e.g. `30s`. Days that
+                         time out or panic count as failures.
+    --in-process         Run all days inside this binary instead of through cargo.
+    --jobs <n>           Run up to <n> days concurrently, printing them in day order.
+                         Defaults to the number of CPUs, but timed runs default to
+                         1 so that concurrent days do not skew the benchmarks.
+    --save-baseline <name>
+                         Store the timings as a baseline in `data/<year>/baselines/`.
+    --baseline <name>    Compare the timings to a baseline.
+    --threshold <pct>    Slowdown that counts as a regression (default: 10).
+  progress             Show the stars of the year and update the stars table in the README.
+    --tests              Run the tests of every solution as well.
+
+Settings such as the data folder, benchmark defaults, submission backend and
+parameters are read from `aoc.toml` and the environment, see the README.
+";
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Extract {
+            puzzle: PuzzleId,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
-            time: bool,
+            settings: RunSettings,
+            format: Option<OutputFormat>,
             submit: Option<u8>,
+            input: Option<String>,
+            params: Vec<String>,
+            watch: bool,
+        },
+        Progress {
+            year: Year,
+            tests: bool,
         },
         All {
+            year: Year,
             release: bool,
-            time: bool,
+            settings: RunSettings,
+            in_process: bool,
+            jobs: Option<usize>,
+            baseline: BaselineArgs,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
+        if args.contains(["-h", "--help"]) {
+            print!("{HELP}");
+            process::exit(0);
+        }
+
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
-                time: args.contains("--time"),
+                settings: parse_run_settings(&mut args)?,
+                in_process: args.contains("--in-process"),
+                jobs: args.opt_value_from_str("--jobs")?,
+                baseline: BaselineArgs {
+                    save: args.opt_value_from_str("--save-baseline")?,
+                    compare: args.opt_value_from_str("--baseline")?,
+                    threshold: args.opt_value_from_str("--threshold")?,
+                },
+            },
+            Some("progress") => AppArguments::Progress {
+                year: parse_year(&mut args)?,
+                tests: args.contains("--tests"),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("extract") => AppArguments::Extract {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                settings: parse_run_settings(&mut args)?,
+                format: args.opt_value_from_str("--format")?,
+                input: args.opt_value_from_str("--input")?,
+                params: args.values_from_str("--param")?,
+                watch: args.contains("--watch"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,25 +174,89 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse the `--year` option, which defaults to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        if let Some(year) = args.opt_value_from_str("--year")? {
+            return Ok(year);
+        }
+
+        match env::var("AOC_YEAR") {
+            Ok(year) => Ok(year.parse()?),
+            Err(_) => {
+                Err("no year specified. Pass --year or set AOC_YEAR, e.g. AOC_YEAR=2023.".into())
+            }
+        }
+    }
+
+    /// Parse the day of a command together with its year.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
+
+    fn parse_run_settings(
+        args: &mut pico_args::Arguments,
+    ) -> Result<RunSettings, pico_args::Error> {
+        Ok(RunSettings {
+            time: args.contains("--time"),
+            bench: BenchSettings {
+                samples: args.opt_value_from_str("--samples")?,
+                time: args.opt_value_from_fn("--bench-time", parse_duration)?,
+            },
+            check: args.contains("--check"),
+            mem: args.contains("--mem"),
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            profile: args.contains("--profile"),
+        })
+    }
 }
 
 fn main() {
+    config::report_unknown_keys();
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                settings,
+                in_process,
+                jobs,
+                baseline,
+            } => all::handle(year, release, settings, in_process, jobs, baseline),
+            AppArguments::Progress { year, tests } => progress::handle(year, tests),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold { puzzle } => scaffold::handle(puzzle),
+            AppArguments::Extract { puzzle } => extract::handle(puzzle),
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
-                time,
+                settings,
+                format,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+                params,
+                watch,
+            } => solve::handle(
+                puzzle,
+                release,
+                settings,
+                SolveArgs {
+                    format,
+                    submit,
+                    input,
+                    params,
+                    watch,
+                },
+            ),
         },
     };
 }
@@ -0,0 +1,228 @@
+/// Sequences that repeat themselves or grow regularly.
+///
+/// A sequence of states is given by its start and a function that moves a state
+/// one step forward in place. If the states repeat, [`brent`] or [`floyd`] find
+/// where, and [`advance`] uses that to skip ahead. [`extrapolate`] predicts a
+/// sequence of numbers from its first few values.
+use std::iter::zip;
+
+/// Where a sequence of states starts repeating: the state after
+/// `prefix_len + period` steps equals the state after `prefix_len` steps, and
+/// both are as small as possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix_len: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The smallest number of steps that ends in the same state as `n` steps.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.prefix_len {
+            n
+        } else {
+            self.prefix_len + (n - self.prefix_len) % self.period
+        }
+    }
+}
+
+/// Find the cycle of the states reached from `start`, using Brent's algorithm.
+/// Never returns if the states don't repeat.
+pub fn brent<S: Clone + Eq>(start: &S, mut step: impl FnMut(&mut S)) -> Cycle {
+    let (_, _, Some(period)) = find_period(start, &mut step, usize::MAX) else {
+        unreachable!("a sequence of usize::MAX steps without a repeat");
+    };
+    let (prefix_len, _) = find_cycle_start(start, &mut step, period);
+
+    Cycle { prefix_len, period }
+}
+
+/// Find the cycle of the states reached from `start`, using Floyd's algorithm.
+/// Never returns if the states don't repeat. [`brent`] needs fewer steps.
+pub fn floyd<S: Clone + Eq>(start: &S, mut step: impl FnMut(&mut S)) -> Cycle {
+    // The hare moves twice as fast as the tortoise, so they meet somewhere in
+    // the cycle, at a number of steps from start that is a multiple of the
+    // period.
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    loop {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    // Moving one from the start and the other from the meeting point, they
+    // meet again where the cycle starts.
+    let mut tortoise = start.clone();
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix_len += 1;
+    }
+
+    let mut period = 1;
+    step(&mut hare);
+    while tortoise != hare {
+        step(&mut hare);
+        period += 1;
+    }
+
+    Cycle { prefix_len, period }
+}
+
+/// The state after `n` steps from `start`. If the states repeat before that,
+/// the steps of whole cycles are skipped.
+pub fn advance<S: Clone + Eq>(start: S, n: usize, mut step: impl FnMut(&mut S)) -> S {
+    let (steps, mut state, period) = find_period(&start, &mut step, n);
+
+    // A repeat means that the state is in the cycle, so the remaining steps
+    // can be reduced modulo the period.
+    if let Some(period) = period {
+        for _ in 0..(n - steps) % period {
+            step(&mut state);
+        }
+    }
+
+    state
+}
+
+/// The first phase of Brent's algorithm. The tortoise waits for the hare at
+/// every power of two steps, until the hare reaches it again. Returns the
+/// number of steps of the hare, its state, and the period unless the hare made
+/// `max_steps` steps first.
+fn find_period<S: Clone + Eq>(
+    start: &S,
+    step: &mut impl FnMut(&mut S),
+    max_steps: usize,
+) -> (usize, S, Option<usize>) {
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    let mut power = 1;
+    let mut period = 0;
+
+    for steps in 1..=max_steps {
+        step(&mut hare);
+        period += 1;
+        if hare == tortoise {
+            return (steps, hare, Some(period));
+        }
+
+        if period == power {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+    }
+
+    (max_steps, hare, None)
+}
+
+/// The second phase of Brent's algorithm. With the hare a period ahead of the
+/// tortoise, they first meet where the cycle starts. Returns the number of
+/// steps to get there and the state there.
+fn find_cycle_start<S: Clone + Eq>(
+    start: &S,
+    step: &mut impl FnMut(&mut S),
+    period: usize,
+) -> (usize, S) {
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..period {
+        step(&mut hare);
+    }
+
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix_len += 1;
+    }
+
+    (prefix_len, tortoise)
+}
+
+/// The value at index `n` of the sequence that starts with `samples`, which
+/// must be a polynomial in the index of a lower degree than the number of
+/// samples. Uses Newton's forward differences, so the values in between are
+/// never computed and `n` may also be negative.
+pub fn extrapolate(samples: &[i64], n: i64) -> i64 {
+    // The first value of every row of the difference table
+    let mut leading_diffs = Vec::with_capacity(samples.len());
+    let mut diffs = samples.to_vec();
+    while let Some(&first) = diffs.first() {
+        leading_diffs.push(first);
+        diffs = diffs.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+
+    // value(n) is the sum over j of binomial(n, j) times the j-th difference
+    let mut value = 0;
+    let mut binomial = 1;
+    for (j, diff) in zip(0.., leading_diffs) {
+        value += binomial * diff;
+        binomial = binomial * (n - j) / (j + 1);
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The powers of 2 modulo 499, which repeat from 1 after 166 steps.
+    fn double_mod(x: &mut u32) {
+        *x = *x * 2 % 499;
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            prefix_len: 0,
+            period: 166,
+        };
+        assert_eq!(brent(&1, double_mod), expected);
+        assert_eq!(floyd(&1, double_mod), expected);
+
+        let rho = |x: &mut u32| *x = if *x < 10 { *x + 1 } else { 3 };
+        let expected = Cycle {
+            prefix_len: 3,
+            period: 8,
+        };
+        assert_eq!(brent(&0, rho), expected);
+        assert_eq!(floyd(&0, rho), expected);
+        assert_eq!(brent(&3, rho).prefix_len, 0);
+        assert_eq!(expected.equivalent_step(2), 2);
+        assert_eq!(expected.equivalent_step(19), 3);
+    }
+
+    #[test]
+    fn advances_past_cycles() {
+        let rho = |x: &mut u32| *x = if *x < 10 { *x + 1 } else { 3 };
+        for n in 0..40 {
+            let mut naive = 0;
+            for _ in 0..n {
+                rho(&mut naive);
+            }
+            assert_eq!(advance(0, n, rho), naive);
+        }
+        assert_eq!(advance(0, 1_000_000_000_000, rho), 8);
+    }
+
+    #[test]
+    fn extrapolates_polynomials() {
+        let squares = [0, 1, 4, 9];
+        assert_eq!(extrapolate(&squares, 2), 4);
+        assert_eq!(extrapolate(&squares, 100), 10_000);
+        assert_eq!(extrapolate(&squares, -3), 9);
+        assert_eq!(extrapolate(&squares[1..], 9), 100);
+
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], 6), 68);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+        assert_eq!(extrapolate(&[7], 1_000), 7);
+        assert_eq!(extrapolate(&[], 1), 0);
+    }
+}
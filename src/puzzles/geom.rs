@@ -0,0 +1,472 @@
+/// Points, vectors and directions in two and three dimensions.
+///
+/// A point is a position and a vector is a displacement: adding a vector to a
+/// point gives a point, and subtracting two points gives the vector between
+/// them. On the plane, `y` grows downwards like the rows of a grid, so
+/// [`Direction::North`] decreases `y`. [`Point::grid_pos`] converts a point to
+/// the `(row, column)` position of [`crate::puzzles::grid`].
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use super::grid::Pos;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// An error which can be returned when parsing a point or vector.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseCoordsError {
+    expected: usize,
+}
+
+impl Display for ParseCoordsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting {} numbers separated by `,`", self.expected)
+    }
+}
+
+impl Error for ParseCoordsError {}
+
+/// Parse comma-separated coordinates, e.g. `1,2,3` or `19, 13, 30`.
+fn parse_coords<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], ParseCoordsError> {
+    let error = || ParseCoordsError { expected: N };
+    let coords = s
+        .split(',')
+        .map(|c| c.trim().parse().map_err(|_| error()))
+        .collect::<Result<Vec<T>, _>>()?;
+
+    coords.try_into().map_err(|_| error())
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+/// The constructors, arithmetic, distances and parsing of a point type and its
+/// vector type.
+macro_rules! coords {
+    ($point:ident, $vector:ident, $($field:ident),+) => {
+        impl<T> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $point { $($field),+ }
+            }
+        }
+
+        impl<T> $vector<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $vector { $($field),+ }
+            }
+        }
+
+        impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> $point<T> {
+            #[must_use]
+            pub fn manhattan_distance(self, other: Self) -> T {
+                let [first, rest @ ..] = [$(abs_diff(self.$field, other.$field)),+];
+                rest.into_iter().fold(first, |sum, d| sum + d)
+            }
+        }
+
+        impl<T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>> $vector<T> {
+            /// The sum of the absolute values of the components.
+            #[must_use]
+            pub fn manhattan_len(self) -> T {
+                let zero = T::default();
+                [$(abs_diff(self.$field, zero)),+]
+                    .into_iter()
+                    .fold(zero, |sum, d| sum + d)
+            }
+        }
+
+        impl<T: Copy + Add<Output = T> + Mul<Output = T>> $vector<T> {
+            #[must_use]
+            pub fn dot(self, other: Self) -> T {
+                let [first, rest @ ..] = [$(self.$field * other.$field),+];
+                rest.into_iter().fold(first, |sum, p| sum + p)
+            }
+        }
+
+        impl<T: Add<Output = T>> Add<$vector<T>> for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: $vector<T>) -> Self {
+                $point { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub<$vector<T>> for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: $vector<T>) -> Self {
+                $point { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = $vector<T>;
+
+            fn sub(self, rhs: Self) -> $vector<T> {
+                $vector { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign<$vector<T>> for $point<T> {
+            fn add_assign(&mut self, rhs: $vector<T>) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign<$vector<T>> for $point<T> {
+            fn sub_assign(&mut self, rhs: $vector<T>) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $vector<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $vector { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $vector<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $vector { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $vector<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $vector<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $vector { $($field: -self.$field),+ }
+            }
+        }
+
+        /// Scale by a number.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $vector<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $vector { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: FromStr> FromStr for $point<T> {
+            type Err = ParseCoordsError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let [$($field),+] = parse_coords(s)?;
+                Ok($point { $($field),+ })
+            }
+        }
+
+        impl<T: FromStr> FromStr for $vector<T> {
+            type Err = ParseCoordsError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let [$($field),+] = parse_coords(s)?;
+                Ok($vector { $($field),+ })
+            }
+        }
+    };
+}
+
+coords!(Point, Vec2, x, y);
+coords!(Point3, Vec3, x, y, z);
+
+impl<T: TryInto<usize>> Point<T> {
+    /// The grid position `(row, column)` of the point, if neither coordinate
+    /// is negative. Whether the position is on the grid is up to the grid.
+    pub fn grid_pos(self) -> Option<Pos> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl From<Pos> for Point<usize> {
+    fn from((row, column): Pos) -> Self {
+        Point { x: column, y: row }
+    }
+}
+
+impl From<Point<usize>> for Pos {
+    fn from(point: Point<usize>) -> Self {
+        (point.y, point.x)
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vec3<T> {
+    #[must_use]
+    pub fn cross(self, other: Self) -> Self {
+        Vec3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// Parse `L` or `R`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            _ => None,
+        }
+    }
+}
+
+/// The four cardinal directions, clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parse a compass letter (`N`, `E`, `S`, `W`) or a screen letter (`U`,
+    /// `R`, `D`, `L`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'N' | 'U' => Some(Direction::North),
+            'E' | 'R' => Some(Direction::East),
+            'S' | 'D' => Some(Direction::South),
+            'W' | 'L' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    #[must_use]
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    /// The vector of one step in this direction.
+    #[must_use]
+    pub fn offset<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+
+    /// The offset of one step in this direction as `(rows, columns)`, for
+    /// [`crate::puzzles::grid::Grid::offset`].
+    #[must_use]
+    pub fn grid_offset(self) -> (isize, isize) {
+        let Vec2 { x, y } = self.offset();
+        (y, x)
+    }
+}
+
+/// The four cardinal and four diagonal directions, clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Turn 45 degrees to the left.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turn 45 degrees to the right.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The vector of one step in this direction.
+    #[must_use]
+    pub fn offset<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+
+    /// The offset of one step in this direction as `(rows, columns)`.
+    #[must_use]
+    pub fn grid_offset(self) -> (isize, isize) {
+        let Vec2 { x, y } = self.offset();
+        (y, x)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!((b - a).manhattan_len(), 7);
+        assert_eq!(-Vec2::new(1, -1) * 3, Vec2::new(-3, 3));
+
+        let v = Vec3::new(1, 0, 0);
+        let w = Vec3::new(0, 1, 0);
+        assert_eq!(v.cross(w), Vec3::new(0, 0, 1));
+        assert_eq!(v.dot(w), 0);
+        assert_eq!(
+            Point3::new(0, 0, 0).manhattan_distance(Point3::new(1, -2, 3)),
+            6
+        );
+    }
+
+    #[test]
+    fn parses_coords() {
+        assert_eq!("1,2,3".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("19, 13, -30".parse(), Ok(Vec3::new(19, 13, -30)));
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert!("1,x".parse::<Point<i32>>().is_err());
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.turn(Turn::Right), Direction::South);
+        assert_eq!(Direction::South.opposite(), Direction::North);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.opposite(), Direction8::NorthWest);
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+    }
+
+    #[test]
+    fn converts_to_grid_positions() {
+        let point = Point::new(2, 0) + Direction::North.offset();
+        assert_eq!(point, Point::new(2, -1));
+        assert_eq!(point.grid_pos(), None);
+        assert_eq!(Point::new(2, 1).grid_pos(), Some((1, 2)));
+        assert_eq!(Direction::West.grid_offset(), (0, -1));
+        assert_eq!(Pos::from(Point::from((1, 2))), (1, 2));
+    }
+}
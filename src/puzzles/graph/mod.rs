@@ -0,0 +1,611 @@
+/// Searches and orderings over graphs.
+///
+/// The algorithms work on anything that implements [`Graph`]: an explicit
+/// [`AdjacencyList`], or an implicit graph whose edges are computed on the fly
+/// with [`from_fn`] or [`unweighted`], e.g. the states of a search.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Add,
+};
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+pub mod union_find;
+
+pub use self::union_find::UnionFind;
+
+/// A directed graph with a cost on every edge. An undirected graph is a graph
+/// with an edge in both directions.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+    type Cost: Copy;
+
+    /// The nodes that can be reached from `node` in one step, with the cost of
+    /// that step.
+    fn successors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, Self::Cost)>;
+}
+
+/// A graph that stores the outgoing edges of every node.
+#[derive(Debug, Clone)]
+pub struct AdjacencyList<N, C = u32> {
+    edges: HashMap<N, Vec<(N, C)>>,
+}
+
+impl<N, C> Default for AdjacencyList<N, C> {
+    fn default() -> Self {
+        AdjacencyList {
+            edges: HashMap::default(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> AdjacencyList<N, C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, cost: C) {
+        self.edges.entry(to.clone()).or_default();
+        self.edges.entry(from).or_default().push((to, cost));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N, cost: C) {
+        self.add_edge(a.clone(), b.clone(), cost);
+        self.add_edge(b, a, cost);
+    }
+
+    /// The nodes at either end of an edge, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    pub fn neighbours(&self, node: &N) -> &[(N, C)] {
+        self.edges.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// The edges as `(from, to, cost)`, in no particular order.
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N, C)> {
+        self.edges
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, cost)| (from, to, *cost)))
+    }
+
+    /// The graph with every edge pointing the other way.
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        self.edges()
+            .map(|(from, to, cost)| (to.clone(), from.clone(), cost))
+            .collect()
+    }
+
+    /// The graph with every edge in both directions.
+    #[must_use]
+    pub fn to_undirected(&self) -> Self {
+        let mut undirected = Self::new();
+        for (from, to, cost) in self.edges() {
+            undirected.add_undirected_edge(from.clone(), to.clone(), cost);
+        }
+        undirected
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> FromIterator<(N, N, C)> for AdjacencyList<N, C> {
+    fn from_iter<I: IntoIterator<Item = (N, N, C)>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        for (from, to, cost) in iter {
+            graph.add_edge(from, to, cost);
+        }
+        graph
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Graph for AdjacencyList<N, C> {
+    type Node = N;
+    type Cost = C;
+
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, C)> {
+        self.neighbours(node).iter().cloned()
+    }
+}
+
+/// A graph whose edges are computed by a function, see [`from_fn`].
+pub struct FromFn<N, C, F> {
+    successors: F,
+    marker: PhantomData<fn(&N) -> C>,
+}
+
+/// A graph with the successors, and the cost of the step to them, given by a
+/// function of the node.
+pub fn from_fn<N, C, I, F>(successors: F) -> FromFn<N, C, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    FromFn {
+        successors,
+        marker: PhantomData,
+    }
+}
+
+impl<N, C, I, F> Graph for FromFn<N, C, F>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    type Node = N;
+    type Cost = C;
+
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, C)> {
+        (self.successors)(node).into_iter()
+    }
+}
+
+/// A graph without costs whose edges are computed by a function, see
+/// [`unweighted`].
+pub struct Unweighted<N, F> {
+    neighbours: F,
+    marker: PhantomData<fn(&N)>,
+}
+
+/// A graph without costs, with the successors given by a function of the
+/// node.
+pub fn unweighted<N, I, F>(neighbours: F) -> Unweighted<N, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Unweighted {
+        neighbours,
+        marker: PhantomData,
+    }
+}
+
+impl<N, I, F> Graph for Unweighted<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Node = N;
+    type Cost = ();
+
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, ())> {
+        (self.neighbours)(node).into_iter().map(|n| (n, ()))
+    }
+}
+
+/// A cheapest path, from the start to the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+/// The nodes discovered by a search, numbered in the order of discovery, with
+/// the number of the node they were reached from and the lowest cost so far.
+struct Discovered<N, C> {
+    numbers: HashMap<N, usize>,
+    nodes: Vec<(N, usize, C)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Discovered<N, C> {
+    fn path(&self, mut number: usize) -> Path<N, C> {
+        let cost = self.nodes[number].2;
+        let mut nodes = vec![self.nodes[number].0.clone()];
+        while self.nodes[number].1 != number {
+            number = self.nodes[number].1;
+            nodes.push(self.nodes[number].0.clone());
+        }
+        nodes.reverse();
+
+        Path { cost, nodes }
+    }
+}
+
+/// Run A* from `start` until a node is reached for which `is_goal` holds. The
+/// heuristic estimates the cost from a node to the goal; the path is the
+/// cheapest one if it never overestimates. The costs must not be negative, and
+/// their default is zero.
+fn search<G, C>(
+    graph: &G,
+    start: G::Node,
+    mut heuristic: impl FnMut(&G::Node) -> C,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> (Discovered<G::Node, C>, Option<usize>)
+where
+    G: Graph<Cost = C>,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let mut discovered = Discovered {
+        numbers: HashMap::default(),
+        nodes: vec![],
+    };
+    let mut frontier = BinaryHeap::new();
+
+    frontier.push(Reverse((heuristic(&start), C::default(), 0)));
+    discovered.numbers.insert(start.clone(), 0);
+    discovered.nodes.push((start, 0, C::default()));
+
+    while let Some(Reverse((_, cost, number))) = frontier.pop() {
+        if cost > discovered.nodes[number].2 {
+            continue; // A cheaper path to this node was found after pushing this one
+        }
+
+        let node = discovered.nodes[number].0.clone();
+        if is_goal(&node) {
+            return (discovered, Some(number));
+        }
+
+        for (successor, step_cost) in graph.successors(&node) {
+            let successor_cost = cost + step_cost;
+            let successor_number = match discovered.numbers.entry(successor) {
+                Entry::Vacant(entry) => {
+                    let successor_number = discovered.nodes.len();
+                    discovered
+                        .nodes
+                        .push((entry.key().clone(), number, successor_cost));
+                    entry.insert(successor_number);
+                    successor_number
+                }
+                Entry::Occupied(entry) => {
+                    let successor_number = *entry.get();
+                    let best = &mut discovered.nodes[successor_number];
+                    if best.2 <= successor_cost {
+                        continue;
+                    }
+                    best.1 = number;
+                    best.2 = successor_cost;
+                    successor_number
+                }
+            };
+
+            let estimate = successor_cost + heuristic(&discovered.nodes[successor_number].0);
+            frontier.push(Reverse((estimate, successor_cost, successor_number)));
+        }
+    }
+
+    (discovered, None)
+}
+
+/// The cheapest path from `start` to a node for which `is_goal` holds, using
+/// Dijkstra's algorithm. The costs must not be negative.
+pub fn dijkstra<G, C>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node, C>>
+where
+    G: Graph<Cost = C>,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    astar(graph, start, |_| C::default(), is_goal)
+}
+
+/// The cheapest path from `start` to a node for which `is_goal` holds, using
+/// the A* algorithm. The heuristic estimates the cost from a node to the goal;
+/// if it never overestimates, the path is the cheapest one.
+pub fn astar<G, C>(
+    graph: &G,
+    start: G::Node,
+    heuristic: impl FnMut(&G::Node) -> C,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node, C>>
+where
+    G: Graph<Cost = C>,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let (discovered, goal) = search(graph, start, heuristic, is_goal);
+    goal.map(|number| discovered.path(number))
+}
+
+/// The cost of the cheapest path from `start` to every node that can be
+/// reached from it, using Dijkstra's algorithm.
+pub fn dijkstra_all<G, C>(graph: &G, start: G::Node) -> HashMap<G::Node, C>
+where
+    G: Graph<Cost = C>,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let (discovered, _) = search(graph, start, |_| C::default(), |_| false);
+    discovered
+        .nodes
+        .into_iter()
+        .map(|(node, _, cost)| (node, cost))
+        .collect()
+}
+
+/// The number of steps from `start` to every node that can be reached from it
+/// in at most `max_steps` steps, ignoring the costs, using a breadth-first
+/// search.
+pub fn bfs_distances<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    max_steps: usize,
+) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::default();
+    distances.insert(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if steps == max_steps {
+            continue;
+        }
+
+        for (successor, _) in graph.successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(successor) {
+                queue.push_back((entry.key().clone(), steps + 1));
+                entry.insert(steps + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+/// An error which is returned when ordering a graph with a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// A node on the cycle.
+    pub node: N,
+}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the graph has a cycle through {:?}", self.node)
+    }
+}
+
+impl<N: Debug> Error for CycleError<N> {}
+
+/// The nodes that can be reached from `starts`, ordered so that every edge
+/// points forward, using a depth-first search.
+pub fn topological_sort<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> Result<Vec<G::Node>, CycleError<G::Node>> {
+    // `finished` is false while the successors of a node are being visited.
+    let mut finished: HashMap<G::Node, bool> = HashMap::default();
+    let mut order = vec![];
+
+    for start in starts {
+        let mut stack = vec![(start, false)];
+        while let Some((node, expanded)) = stack.pop() {
+            if expanded {
+                finished.insert(node.clone(), true);
+                order.push(node);
+                continue;
+            }
+
+            match finished.get(&node) {
+                Some(true) => continue,
+                // Only a successor of the node itself can push it again
+                // before it is finished.
+                Some(false) => return Err(CycleError { node }),
+                None => (),
+            }
+
+            finished.insert(node.clone(), false);
+            stack.push((node.clone(), true));
+            for (successor, _) in graph.successors(&node) {
+                if finished.get(&successor) != Some(&true) {
+                    stack.push((successor, false));
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// The sets of nodes that are connected to each other, for a graph in which
+/// every edge goes both ways.
+pub fn connected_components<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    let mut visited = HashSet::default();
+    let mut components = vec![];
+
+    for start in nodes {
+        if !visited.insert(start.clone()) {
+            continue;
+        }
+
+        let mut component = vec![start.clone()];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for (successor, _) in graph.successors(&node) {
+                if visited.insert(successor.clone()) {
+                    component.push(successor.clone());
+                    stack.push(successor);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// The sets of nodes that can all reach each other, using Tarjan's algorithm.
+/// Every component comes after the components it has edges to.
+pub fn strongly_connected_components<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    // Nodes are numbered in the order they are visited. `lowest` is the lowest
+    // number that a node is known to reach among the nodes on `stack`.
+    let mut numbers: HashMap<G::Node, usize> = HashMap::default();
+    let mut lowest: Vec<usize> = vec![];
+    let mut on_stack: Vec<bool> = vec![];
+    let mut stack: Vec<(usize, G::Node)> = vec![];
+    let mut components = vec![];
+
+    for root in nodes {
+        if numbers.contains_key(&root) {
+            continue;
+        }
+
+        // The nodes being visited, with their successors and how many of them
+        // have been visited.
+        let mut visiting: Vec<(usize, Vec<G::Node>, usize)> = vec![];
+        let mut next = Some(root);
+
+        loop {
+            if let Some(node) = next.take() {
+                let number = lowest.len();
+                numbers.insert(node.clone(), number);
+                lowest.push(number);
+                on_stack.push(true);
+                let successors = graph.successors(&node).map(|(n, _)| n).collect();
+                stack.push((number, node));
+                visiting.push((number, successors, 0));
+            }
+
+            let Some((number, successors, visited)) = visiting.last_mut() else {
+                break;
+            };
+            let number = *number;
+
+            if let Some(successor) = successors.get(*visited) {
+                *visited += 1;
+                match numbers.get(successor) {
+                    None => next = Some(successor.clone()),
+                    Some(&successor_number) if on_stack[successor_number] => {
+                        lowest[number] = lowest[number].min(successor_number);
+                    }
+                    Some(_) => (),
+                }
+                continue;
+            }
+
+            visiting.pop();
+            if let Some(&(parent, _, _)) = visiting.last() {
+                lowest[parent] = lowest[parent].min(lowest[number]);
+            }
+
+            if lowest[number] == number {
+                let mut component = vec![];
+                while let Some((member_number, member)) = stack.pop() {
+                    on_stack[member_number] = false;
+                    component.push(member);
+                    if member_number == number {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 -> 2 -> 4 is cheaper than 1 -> 4, and 1 -> 3 -> 4 is cheaper still.
+    fn diamond() -> AdjacencyList<u32> {
+        [(1, 2, 1), (2, 4, 5), (1, 4, 7), (1, 3, 2), (3, 4, 3)]
+            .into_iter()
+            .collect()
+    }
+
+    fn sorted(mut components: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let graph = diamond();
+        let path = dijkstra(&graph, 1, |&n| n == 4).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                cost: 5,
+                nodes: vec![1, 3, 4]
+            }
+        );
+        assert_eq!(dijkstra(&graph, 4, |&n| n == 1), None);
+
+        let path = astar(&graph, 1, |&n| 4 - n, |&n| n == 4).unwrap();
+        assert_eq!(path.cost, 5);
+
+        let costs = dijkstra_all(&graph.reversed(), 4);
+        assert_eq!(costs[&1], 5);
+        assert_eq!(costs[&2], 5);
+    }
+
+    #[test]
+    fn searches_implicit_graphs() {
+        // Reach 10 from 1 by adding 1 (cost 1) or doubling (cost 2): 1, 2, 4, 5, 10.
+        let graph = from_fn(|&n: &u32| [(n + 1, 1), (n * 2, 2)]);
+        let path = dijkstra(&graph, 1, |&n| n == 10).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes.first(), Some(&1));
+        assert_eq!(path.nodes.last(), Some(&10));
+
+        let line = unweighted(|&n: &i32| [n - 1, n + 1]);
+        let distances = bfs_distances(&line, 0, 3);
+        assert_eq!(distances.len(), 7);
+        assert_eq!(distances[&-3], 3);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let order = topological_sort(&diamond(), [1]).unwrap();
+        let position = |n| order.iter().position(|&m| m == n).unwrap();
+        assert_eq!(order.len(), 4);
+        for (from, to, _) in diamond().edges() {
+            assert!(position(*from) < position(*to));
+        }
+
+        let mut cyclic = diamond();
+        cyclic.add_edge(4, 1, 1);
+        assert!(topological_sort(&cyclic, [1]).is_err());
+    }
+
+    #[test]
+    fn finds_components() {
+        let mut graph = diamond().to_undirected();
+        graph.add_undirected_edge(5, 6, 1);
+        let nodes: Vec<u32> = graph.nodes().copied().collect();
+        assert_eq!(
+            sorted(connected_components(&graph, nodes)),
+            vec![vec![1, 2, 3, 4], vec![5, 6]]
+        );
+
+        let mut graph = diamond();
+        graph.add_edge(4, 2, 1);
+        graph.add_edge(5, 1, 1);
+        let components = strongly_connected_components(&graph, [5, 1, 2, 3, 4]);
+        assert_eq!(
+            sorted(components.clone()),
+            vec![vec![1], vec![2, 4], vec![3], vec![5]]
+        );
+        // Components come after the components they point to.
+        assert_eq!(components.first(), Some(&vec![4, 2]));
+        assert_eq!(components.last(), Some(&vec![5]));
+    }
+}
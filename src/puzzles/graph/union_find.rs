@@ -0,0 +1,175 @@
+/// Union-Find container data structure
+use rustc_hash::FxHashMap as HashMap;
+use std::hash::Hash;
+
+// Invariants:
+// - idx2elem holds only valid pointers to keys of elem2idx. If elem2idx
+//   would be moved/mutated without updating idx2elem, this could lead to
+//   dereferencing an invalid pointer.
+// - the values of elem2idx are unique and in the range 0..elems.len().
+// - the values of parents are unique and in the range 0..elems.len().
+// - elem2idx, idx2elem, parents and sizes have equal length.
+pub struct UnionFind<T> {
+    elem2idx: HashMap<T, usize>, // key: element, value: index into elems, parents and sizes
+    idx2elem: Vec<*const T>,     // translates index back to element. Points into elem2idx.
+    parents: Vec<usize>,         // parent idx of each node idx (equal to itself for root)
+    sizes: Vec<usize>,           // size of subtree (including node itself) of each node
+    n_sets: usize,
+}
+
+impl<T> FromIterator<T> for UnionFind<T>
+where
+    T: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elem2idx: HashMap<T, usize> = iter
+            .into_iter()
+            .enumerate()
+            .map(|(idx, elem)| (elem, idx))
+            .collect();
+        let idx2elem = Vec::new();
+        let parents = (0..elem2idx.len()).collect();
+        let sizes = vec![1; elem2idx.len()];
+        let n_sets = elem2idx.len();
+
+        let mut uf = UnionFind {
+            elem2idx,
+            idx2elem,
+            parents,
+            sizes,
+            n_sets,
+        };
+
+        // From now on, idx2elem has its location inside uf, and mutating a
+        // field of uf will not move uf. So at this point we can safely
+        // create the idx2elem vec.
+        let mut idx2elem: Vec<(usize, *const T)> = uf
+            .elem2idx
+            .iter()
+            .map(|(elem, &idx)| (idx, elem as *const T))
+            .collect();
+        idx2elem.sort_by_key(|x| x.0);
+
+        let idx2elem: Vec<*const T> = idx2elem.into_iter().map(|(_i, p)| p).collect();
+        uf.idx2elem = idx2elem;
+
+        uf
+    }
+}
+
+impl<T> UnionFind<T>
+where
+    T: Eq + Hash + PartialEq,
+{
+    /// Find this element and (if found) return the root element of the set
+    /// it is in. Unlike find_root_shorten, this requires no mutable access.
+    pub fn find_root(&self, elem: &T) -> Option<&T> {
+        let root_idx = self.find_root_idx(elem)?;
+        unsafe { Some(&*self.idx2elem[root_idx]) }
+    }
+
+    fn find_root_idx(&self, elem: &T) -> Option<usize> {
+        let mut walk = *self.elem2idx.get(elem)?;
+        loop {
+            let parent = self.parents[walk];
+            if walk == parent {
+                return Some(walk);
+            }
+            walk = parent;
+        }
+    }
+
+    /// Find this element and (if found) return the root element of the set
+    /// it is in. Also shorten the trees for better efficiency of future
+    /// operations.
+    pub fn find_root_shorten(&mut self, elem: &T) -> Option<&T> {
+        let root_idx = self.find_root_idx_shorten(elem)?;
+        unsafe { Some(&*self.idx2elem[root_idx]) }
+    }
+
+    fn find_root_idx_shorten(&mut self, elem: &T) -> Option<usize> {
+        // First pass: find root.
+        let start_idx = *self.elem2idx.get(elem)?;
+        let mut walk = start_idx;
+        loop {
+            let parent = self.parents[walk];
+            if walk == parent {
+                break;
+            }
+            walk = parent;
+        }
+        let root_idx = walk;
+
+        // Second pass: move all children directly under the root.
+        let mut walk = start_idx;
+        while walk != root_idx {
+            let next = self.parents[walk];
+            self.parents[walk] = root_idx;
+            self.sizes[walk] = 1;
+            walk = next;
+        }
+
+        Some(root_idx)
+    }
+
+    /// Union the sets containing each element, returning None if they were
+    /// already part of the same set or if either one is not in the
+    /// container, and returning the root element of the unioned set if the
+    /// merge was successful.
+    pub fn union(&mut self, elem1: &T, elem2: &T) -> Option<&T> {
+        let root_idx1 = self.find_root_idx_shorten(elem1)?;
+        let root_idx2 = self.find_root_idx_shorten(elem2)?;
+        if root_idx1 == root_idx2 {
+            return None;
+        }
+
+        let result_idx = if self.sizes[root_idx1] > self.sizes[root_idx2] {
+            self.parents[root_idx2] = root_idx1;
+            self.sizes[root_idx1] += self.sizes[root_idx2];
+            self.n_sets -= 1;
+            root_idx1
+        } else {
+            self.parents[root_idx1] = root_idx2;
+            self.sizes[root_idx2] += self.sizes[root_idx1];
+            self.n_sets -= 1;
+            root_idx2
+        };
+        unsafe { Some(&*self.idx2elem[result_idx]) }
+    }
+
+    /// The size of the set that elem is part of.
+    pub fn size_of_set(&self, elem: &T) -> Option<usize> {
+        let root_idx = self.find_root_idx(elem)?;
+        Some(self.sizes[root_idx])
+    }
+
+    pub fn n_sets(&self) -> usize {
+        self.n_sets
+    }
+
+    pub fn len(&self) -> usize {
+        self.elem2idx.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elem2idx.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortens_whole_path() {
+        let mut uf: UnionFind<u32> = (0..5).collect();
+        // a chain 0 -> 1 -> 2 -> 3 -> 4, which union by size never builds.
+        uf.parents = vec![1, 2, 3, 4, 4];
+        uf.sizes = vec![1, 2, 3, 4, 5];
+        uf.n_sets = 1;
+
+        assert_eq!(uf.find_root_shorten(&0), Some(&4));
+        assert_eq!(uf.parents, vec![4; 5]);
+        assert_eq!(uf.size_of_set(&2), Some(5));
+    }
+}
@@ -0,0 +1,342 @@
+/// A two-dimensional grid of cells, as parsed from the character grids of many
+/// puzzles. Positions are `(row, column)` pairs, like the indices of ndarray.
+///
+/// `Grid<T>` dereferences to its [`Array2`], so views such as `row`, `column`,
+/// `rows_mut` or `t` (the transposed view), and iterators such as
+/// `indexed_iter`, come straight from ndarray.
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use ndarray::Array2;
+
+/// A position in a grid as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// The offsets of the four neighbours of a position as `(rows, columns)`, in
+/// the order north, east, south, west.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The offsets of the eight neighbours of a position, clockwise from north.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    /// A row that is not as long as the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        pos: Pos,
+        c: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "the grid is empty"),
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} of the grid has {found} cells, expecting {expected}"
+            ),
+            ParseGridError::InvalidCell { pos, c } => {
+                write!(f, "invalid cell {c:?} at {pos:?}")
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    #[must_use]
+    pub fn from_array(cells: Array2<T>) -> Self {
+        Grid { cells }
+    }
+
+    #[must_use]
+    pub fn from_elem(dim: Pos, elem: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: Array2::from_elem(dim, elem),
+        }
+    }
+
+    /// Parse a rectangular grid of characters, converting every character
+    /// with `cell`. Line endings may be `\n` or `\r\n`, and trailing empty
+    /// lines are ignored.
+    pub fn parse_with(
+        s: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let lines: Vec<&str> = s.trim_end_matches(['\n', '\r']).lines().collect();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(ParseGridError::Empty);
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(ParseGridError::Ragged {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+
+            for (column, c) in line.chars().enumerate() {
+                let pos = (row, column);
+                cells.push(cell(c).ok_or(ParseGridError::InvalidCell { pos, c })?);
+            }
+        }
+
+        let cells = Array2::from_shape_vec((lines.len(), width), cells)
+            .expect("every row has the same width");
+        Ok(Grid { cells })
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    #[must_use]
+    pub fn into_array(self) -> Array2<T> {
+        self.cells
+    }
+
+    /// The cell at a position, if it is on the grid.
+    #[must_use]
+    pub fn get_pos(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    /// The position of a signed position, if it is on the grid.
+    #[must_use]
+    pub fn checked_pos(&self, (row, column): (isize, isize)) -> Option<Pos> {
+        let pos = (usize::try_from(row).ok()?, usize::try_from(column).ok()?);
+        (pos.0 < self.height() && pos.1 < self.width()).then_some(pos)
+    }
+
+    /// The position `offset` away from `pos`, if it is on the grid.
+    #[must_use]
+    pub fn offset(&self, pos: Pos, offset: (isize, isize)) -> Option<Pos> {
+        let pos = (
+            pos.0.checked_add_signed(offset.0)?,
+            pos.1.checked_add_signed(offset.1)?,
+        );
+        (pos.0 < self.height() && pos.1 < self.width()).then_some(pos)
+    }
+
+    /// The horizontal and vertical neighbours of a position that are on the
+    /// grid, in the order of [`DIRECTIONS_4`].
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The horizontal, vertical and diagonal neighbours of a position that are
+    /// on the grid, in the order of [`DIRECTIONS_8`].
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The position of a signed position on a grid that repeats infinitely in
+    /// every direction.
+    #[must_use]
+    pub fn wrap(&self, (row, column): (isize, isize)) -> Pos {
+        let (height, width) = (self.height() as isize, self.width() as isize);
+        (
+            row.rem_euclid(height) as usize,
+            column.rem_euclid(width) as usize,
+        )
+    }
+
+    /// The cell at a signed position on a grid that repeats infinitely in
+    /// every direction.
+    #[must_use]
+    pub fn get_wrapping(&self, pos: (isize, isize)) -> &T {
+        &self.cells[self.wrap(pos)]
+    }
+
+    /// Whether a position is on the outer rows or columns of the grid.
+    #[must_use]
+    pub fn is_on_edge(&self, pos: Pos) -> bool {
+        pos.0 == 0 || pos.1 == 0 || pos.0 == self.height() - 1 || pos.1 == self.width() - 1
+    }
+
+    /// The position of the first cell in row-major order that matches.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .indexed_iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// The grid with its rows and columns swapped.
+    #[must_use]
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.cells.t().to_owned(),
+        }
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.map(f),
+        }
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = Array2<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cells
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cells
+    }
+}
+
+/// A grid of ASCII bytes.
+impl FromStr for Grid<u8> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, |c| u8::try_from(c).ok().filter(u8::is_ascii))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, Some)
+    }
+}
+
+/// The grid as text, one line per row.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.rows() {
+            let line: String = row.iter().map(|&c| c.into()).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#.S\r\n..#\r\n\r\n";
+
+    #[test]
+    fn parses_text() {
+        let grid: Grid<u8> = TEXT.parse().unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(0, 2)], b'S');
+        assert_eq!(grid.position(|&c| c == b'S'), Some((0, 2)));
+        assert_eq!(grid.to_string(), "#.S\n..#\n");
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.column(1).to_vec(), vec![2, 4]);
+    }
+
+    #[test]
+    fn rejects_invalid_text() {
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!(
+            "ab\nabc\n".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidCell {
+                pos: (1, 1),
+                c: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid: Grid<u8> = TEXT.parse().unwrap();
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours_8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]
+        );
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+        assert_eq!(grid.checked_pos((-1, 0)), None);
+        assert_eq!(grid.checked_pos((1, 2)), Some((1, 2)));
+        assert!(grid.is_on_edge((1, 1)));
+    }
+
+    #[test]
+    fn wraps_positions() {
+        let grid: Grid<u8> = TEXT.parse().unwrap();
+        assert_eq!(grid.wrap((-1, -1)), (1, 2));
+        assert_eq!(grid.wrap((4, 7)), (0, 1));
+        assert_eq!(*grid.get_wrapping((2, -1)), b'S');
+    }
+
+    #[test]
+    fn transposes() {
+        let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+        assert_eq!(grid.transposed().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.transposed().transposed(), grid);
+    }
+}
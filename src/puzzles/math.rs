@@ -0,0 +1,403 @@
+/// Number theory and exact arithmetic.
+///
+/// Solutions that would otherwise need floating point numbers, or need the
+/// input to line up nicely, can use these instead: congruences that combine
+/// with the Chinese Remainder Theorem, exact fractions, the exact solutions of
+/// linear systems and the area of polygons on a grid.
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    iter::zip,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use num_integer::{ExtendedGcd, Integer};
+
+use crate::puzzles::geom::Point;
+
+/// The integer square root, i.e. the square root rounded down.
+pub use num_integer::sqrt as isqrt;
+
+/// The numbers that leave a residue when divided by a modulus, e.g. the steps
+/// at which something happens that repeats periodically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Congruence {
+    residue: i64,
+    modulus: i64,
+}
+
+impl Congruence {
+    /// Panics if the modulus is not positive.
+    pub fn new(residue: i64, modulus: i64) -> Self {
+        assert!(modulus > 0, "the modulus should be positive");
+        Congruence {
+            residue: residue.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    /// The residue, between 0 and the modulus.
+    pub fn residue(&self) -> i64 {
+        self.residue
+    }
+
+    pub fn modulus(&self) -> i64 {
+        self.modulus
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        n.rem_euclid(self.modulus) == self.residue
+    }
+
+    /// The smallest number in the congruence that is at least `min`.
+    pub fn first_from(&self, min: i64) -> i64 {
+        min + (self.residue - min).rem_euclid(self.modulus)
+    }
+
+    /// The numbers in both congruences, using the Chinese Remainder Theorem
+    /// generalized to moduli that are not coprime. None if there are no such
+    /// numbers. Panics if the combined modulus doesn't fit in an i64.
+    pub fn intersect(&self, other: &Congruence) -> Option<Congruence> {
+        let (r1, m1) = (i128::from(self.residue), i128::from(self.modulus));
+        let (r2, m2) = (i128::from(other.residue), i128::from(other.modulus));
+
+        // Solve r1 + k * m1 == r2 (mod m2) for k, where x * m1 == gcd (mod m2).
+        let ExtendedGcd { gcd, x, .. } = m1.extended_gcd(&m2);
+        if (r2 - r1) % gcd != 0 {
+            return None;
+        }
+        let k = ((r2 - r1) / gcd * x).rem_euclid(m2 / gcd);
+        let modulus = m1 / gcd * m2;
+
+        Some(Congruence {
+            residue: i64::try_from(r1 + k * m1).unwrap(),
+            modulus: i64::try_from(modulus).expect("modulus should fit in an i64"),
+        })
+    }
+}
+
+/// The numbers in all congruences, see [`Congruence::intersect`].
+pub fn crt(congruences: impl IntoIterator<Item = Congruence>) -> Option<Congruence> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1), |all, congruence| {
+            all.intersect(&congruence)
+        })
+}
+
+/// A fraction in lowest terms with a positive denominator. The default is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Integer + Copy + From<i8>> Rational<T> {
+    /// Panics if the denominator is 0.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(denom != T::from(0), "the denominator should not be 0");
+        if denom == T::from(1) {
+            return Rational::from(numer);
+        }
+
+        let gcd = gcd(numer, denom);
+        let sign = if denom < T::from(0) {
+            T::from(-1)
+        } else {
+            T::from(1)
+        };
+
+        Rational {
+            numer: sign * numer / gcd,
+            denom: sign * denom / gcd,
+        }
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::from(1)
+    }
+
+    /// The largest integer that is not larger than the fraction.
+    pub fn floor(&self) -> T {
+        self.numer.div_floor(&self.denom)
+    }
+
+    /// 1 divided by the fraction. Panics if the fraction is 0.
+    pub fn recip(&self) -> Self {
+        Rational::new(self.denom, self.numer)
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Default for Rational<T> {
+    fn default() -> Self {
+        Rational::from(T::from(0))
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> From<T> for Rational<T> {
+    fn from(n: T) -> Self {
+        Rational {
+            numer: n,
+            denom: T::from(1),
+        }
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.denom == other.denom {
+            return Rational::new(self.numer + other.numer, self.denom);
+        }
+
+        let denom = self.denom / gcd(self.denom, other.denom) * other.denom;
+        let numer = self.numer * (denom / self.denom) + other.numer * (denom / other.denom);
+        Rational::new(numer, denom)
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        if self.denom == other.denom {
+            return Rational::new(self.numer - other.numer, self.denom);
+        }
+
+        let denom = self.denom / gcd(self.denom, other.denom) * other.denom;
+        let numer = self.numer * (denom / self.denom) - other.numer * (denom / other.denom);
+        Rational::new(numer, denom)
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if self.is_integer() && other.is_integer() {
+            return Rational::from(self.numer * other.numer);
+        }
+
+        // Cancelling crosswise first keeps the products small.
+        let gcd_1 = gcd(self.numer, other.denom);
+        let gcd_2 = gcd(other.numer, self.denom);
+        Rational::new(
+            (self.numer / gcd_1) * (other.numer / gcd_2),
+            (self.denom / gcd_2) * (other.denom / gcd_1),
+        )
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Div for Rational<T> {
+    type Output = Self;
+
+    /// Panics if `other` is 0.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.recip()
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer + Copy + From<i8>> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl<T: Display + PartialEq + From<i8>> Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == T::from(1) {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// The greatest common divisor, using Euclid's algorithm. For numbers of very
+/// different sizes, this takes fewer steps than the binary algorithm of
+/// [`Integer::gcd`].
+fn gcd<T: Integer + Copy + From<i8>>(mut a: T, mut b: T) -> T {
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+
+    if a < T::from(0) {
+        T::from(0) - a
+    } else {
+        a
+    }
+}
+
+/// The exact solution `x` of `a x = b`, where the square matrix `a` is given
+/// by its rows. None if `a` is singular, i.e. if there is no solution or there
+/// are many.
+///
+/// Uses fraction-free Gaussian elimination (the Bareiss algorithm): the
+/// numbers stay integers, because every division is exact, and only become
+/// fractions in the last step. This is faster than working with fractions
+/// throughout.
+pub fn solve_linear<T: Integer + Copy + From<i8>>(
+    mut a: Vec<Vec<T>>,
+    mut b: Vec<T>,
+) -> Option<Vec<Rational<T>>> {
+    let n = b.len();
+    assert!(
+        a.len() == n && a.iter().all(|row| row.len() == n),
+        "a should be a square matrix with as many rows as b"
+    );
+
+    // Eliminate column by column, until a is diagonal. Every number on the
+    // diagonal is then the determinant, up to its sign.
+    let mut prev_pivot = T::from(1);
+    for col in 0..n {
+        let pivot_idx = (col..n).find(|&row| a[row][col] != T::from(0))?;
+        a.swap(col, pivot_idx);
+        b.swap(col, pivot_idx);
+        let (pivot_row, pivot_b) = (a[col].clone(), b[col]);
+        let pivot = pivot_row[col];
+
+        for (row, (values, b_value)) in zip(&mut a, &mut b).enumerate() {
+            if row == col {
+                continue;
+            }
+
+            let factor = values[col];
+            for (value, &pivot_row_value) in zip(values, &pivot_row) {
+                *value = (pivot * *value - factor * pivot_row_value) / prev_pivot;
+            }
+            *b_value = (pivot * *b_value - factor * pivot_b) / prev_pivot;
+        }
+        prev_pivot = pivot;
+    }
+
+    Some(
+        zip(&a, b)
+            .enumerate()
+            .map(|(i, (row, b))| Rational::new(b, row[i]))
+            .collect(),
+    )
+}
+
+/// Twice the area of the polygon with `vertices` in order along its edges,
+/// using the Shoelace formula. Twice, because the area of a polygon on integer
+/// coordinates is a multiple of a half.
+pub fn polygon_double_area(vertices: &[Point<i64>]) -> i64 {
+    zip(vertices, vertices.iter().cycle().skip(1))
+        .map(|(vert, next_vert)| vert.x * next_vert.y - next_vert.x * vert.y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// The number of points with integer coordinates on the edges of the polygon
+/// with `vertices` in order along its edges.
+pub fn polygon_boundary_points(vertices: &[Point<i64>]) -> i64 {
+    zip(vertices, vertices.iter().cycle().skip(1))
+        .map(|(&vert, &next_vert)| {
+            let edge = next_vert - vert;
+            edge.x.abs().gcd(&edge.y.abs())
+        })
+        .sum()
+}
+
+/// The number of points with integer coordinates strictly inside the polygon
+/// with `vertices` in order along its edges, using Pick's theorem.
+pub fn polygon_interior_points(vertices: &[Point<i64>]) -> i64 {
+    (polygon_double_area(vertices) - polygon_boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_congruences() {
+        let combined = crt([Congruence::new(2, 3), Congruence::new(3, 5)]).unwrap();
+        assert_eq!(combined, Congruence::new(8, 15));
+
+        // 4 and 6 are not coprime, so the residues must agree modulo 2.
+        let combined = Congruence::new(1, 4).intersect(&Congruence::new(3, 6));
+        assert_eq!(combined, Some(Congruence::new(9, 12)));
+        assert_eq!(
+            Congruence::new(1, 4).intersect(&Congruence::new(2, 6)),
+            None
+        );
+
+        let every_step = crt([Congruence::new(0, 4), Congruence::new(-6, 6)]).unwrap();
+        assert_eq!(every_step.residue(), 0);
+        assert_eq!(every_step.first_from(1), 12);
+        assert_eq!(every_step.first_from(-13), -12);
+        assert!(every_step.contains(36));
+        assert_eq!(crt([]), Some(Congruence::new(0, 1)));
+        assert_eq!(isqrt(99u64), 9);
+    }
+
+    #[test]
+    fn calculates_with_fractions() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numer(), half.denom()), (-1, 2));
+        assert_eq!(half + Rational::new(5, 6), Rational::new(1, 3));
+        assert_eq!(half - Rational::new(5, 6), Rational::new(-4, 3));
+        assert_eq!(half * Rational::new(4, 3), Rational::new(-2, 3));
+        assert_eq!(half / Rational::new(-1, 4), Rational::from(2));
+        assert_eq!(Rational::new(-7, 2).floor(), -4);
+        assert!(Rational::new(-7, 2) < half);
+        assert!(Rational::new(5, 3) > Rational::new(3, 2));
+        assert_eq!(Rational::new(5, 3).to_string(), "5/3");
+        assert_eq!(Rational::new(6, 3).to_string(), "2");
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        let r = |rows: [[i128; 3]; 3]| rows.map(Vec::from).to_vec();
+        let b = vec![8, -11, -3];
+
+        let a = r([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        let x = solve_linear(a, b.clone()).unwrap();
+        assert_eq!(x, [2, 3, -1].map(Rational::from));
+
+        // The pivot of the first column is in the last row
+        let a = r([[0, 1, 1], [0, 2, 1], [3, 0, 0]]);
+        let x = solve_linear(a, b.clone()).unwrap();
+        assert_eq!(
+            x,
+            [
+                Rational::new(-1, 1),
+                Rational::from(-19),
+                Rational::from(27)
+            ]
+        );
+
+        let a = r([[1, 2, 3], [2, 4, 6], [1, 0, 0]]);
+        assert_eq!(solve_linear(a, b), None);
+    }
+
+    #[test]
+    fn measures_polygons() {
+        // A 4x4 square, and a triangle with only its corners on the grid.
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(|(x, y)| Point::new(x, y));
+        assert_eq!(polygon_double_area(&square), 32);
+        assert_eq!(polygon_boundary_points(&square), 16);
+        assert_eq!(polygon_interior_points(&square), 9);
+
+        let triangle = [(0, 0), (3, 1), (1, 2)].map(|(x, y)| Point::new(x, y));
+        assert_eq!(polygon_double_area(&triangle), 5);
+        assert_eq!(polygon_boundary_points(&triangle), 3);
+        assert_eq!(polygon_interior_points(&triangle), 2);
+    }
+}
@@ -1,10 +1,10 @@
 use std::collections::HashSet;
 
 use self::tok::{TokenKind, Tokenizer};
-use ndarray::Array2;
+use crate::puzzles::{geom::Point, grid::Grid};
 
 pub struct Schematic {
-    grid: Array2<GridSlot>,
+    grid: Grid<GridSlot>,
     symbols: Vec<Symbol>,
     numbers: Vec<Number>,
 }
@@ -13,10 +13,8 @@ impl Schematic {
     pub fn new(text: &str) -> Self {
         let tokenizer = Tokenizer::new(text);
 
-        // Assume square grid. This is not in the puzzle spec but true for the
-        // example and my input.
-        let mut grid = Array2::from_elem(
-            (tokenizer.line_len(), tokenizer.line_len()),
+        let mut grid = Grid::from_elem(
+            (text.lines().count(), tokenizer.line_len()),
             GridSlot::Empty,
         );
         let mut symbols = Vec::new();
@@ -29,13 +27,12 @@ impl Schematic {
                 TokenKind::SymTok => {
                     let symbol = Symbol {
                         idx: idx_symbol,
-                        x: token.x,
-                        y: token.y,
+                        pos: Point::new(token.x, token.y),
                         char: token.txt.chars().nth(0).unwrap(),
                     };
+                    grid[(token.y, token.x)] = GridSlot::Symbol(idx_symbol);
                     symbols.push(symbol);
 
-                    grid[[token.x, token.y]] = GridSlot::Symbol(idx_symbol);
                     idx_symbol += 1;
                 }
 
@@ -47,7 +44,7 @@ impl Schematic {
                     numbers.push(number);
 
                     for x in token.x..(token.x + token.txt.len()) {
-                        grid[[x, token.y]] = GridSlot::Number(idx_number);
+                        grid[(token.y, x)] = GridSlot::Number(idx_number);
                     }
                     idx_number += 1;
                 }
@@ -61,7 +58,7 @@ impl Schematic {
         }
     }
 
-    pub fn grid(&self) -> &Array2<GridSlot> {
+    pub fn grid(&self) -> &Grid<GridSlot> {
         &self.grid
     }
 
@@ -78,9 +75,9 @@ impl Schematic {
 
         // Mark all numbers adjacent to a symbol in `selected_nums`
         for sym in self.symbols() {
-            for (x, y) in Self::get_adjacent_coords(sym.x, sym.y) {
-                if let Some(GridSlot::Number(i)) = self.grid.get([x, y]) {
-                    selected_nums[*i] = true;
+            for pos in self.grid.neighbours_8(sym.pos.into()) {
+                if let GridSlot::Number(i) = self.grid[pos] {
+                    selected_nums[i] = true;
                 }
             }
         }
@@ -95,8 +92,8 @@ impl Schematic {
         for sym in self.symbols() {
             let mut adjacent_part_idxs = HashSet::new();
 
-            for (x, y) in Self::get_adjacent_coords(sym.x, sym.y) {
-                if let Some(GridSlot::Number(i)) = self.grid.get([x, y]) {
+            for pos in self.grid.neighbours_8(sym.pos.into()) {
+                if let GridSlot::Number(i) = &self.grid[pos] {
                     if part_num_selection[*i] {
                         adjacent_part_idxs.insert(i);
                     }
@@ -113,19 +110,6 @@ impl Schematic {
 
         total_gear_ratio
     }
-
-    fn get_adjacent_coords(x: usize, y: usize) -> [(usize, usize); 8] {
-        [
-            (x - 1, y - 1),
-            (x, y - 1),
-            (x + 1, y - 1),
-            (x - 1, y),
-            (x + 1, y),
-            (x - 1, y + 1),
-            (x, y + 1),
-            (x + 1, y + 1),
-        ]
-    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -138,8 +122,7 @@ pub enum GridSlot {
 #[derive(Debug, Eq, PartialEq)]
 pub struct Symbol {
     pub idx: usize,
-    pub x: usize,
-    pub y: usize,
+    pub pos: Point<usize>,
     pub char: char,
 }
 
@@ -258,20 +241,21 @@ mod tok {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::day;
     use crate::template::read_file;
+    use crate::{day, year, PuzzleId};
 
     #[test]
     fn test_new_schematic() {
-        let schematic = Schematic::new(&read_file("examples", day!(3)));
+        let schematic =
+            Schematic::new(&read_file("examples", PuzzleId::new(year!(2023), day!(3))).unwrap());
 
-        assert_eq!(schematic.grid[[0, 0]], GridSlot::Number(0));
-        assert_eq!(schematic.grid[[1, 0]], GridSlot::Number(0));
-        assert_eq!(schematic.grid[[2, 0]], GridSlot::Number(0));
-        assert_eq!(schematic.grid[[3, 0]], GridSlot::Empty);
-        assert_eq!(schematic.grid[[4, 0]], GridSlot::Empty);
-        assert_eq!(schematic.grid[[5, 0]], GridSlot::Number(1));
-        assert_eq!(schematic.grid[[6, 3]], GridSlot::Symbol(1));
+        assert_eq!(schematic.grid[(0, 0)], GridSlot::Number(0));
+        assert_eq!(schematic.grid[(0, 1)], GridSlot::Number(0));
+        assert_eq!(schematic.grid[(0, 2)], GridSlot::Number(0));
+        assert_eq!(schematic.grid[(0, 3)], GridSlot::Empty);
+        assert_eq!(schematic.grid[(0, 4)], GridSlot::Empty);
+        assert_eq!(schematic.grid[(0, 5)], GridSlot::Number(1));
+        assert_eq!(schematic.grid[(3, 6)], GridSlot::Symbol(1));
 
         let expected_numbers = vec![
             Number { idx: 0, val: 467 },
@@ -290,38 +274,32 @@ mod tests {
         let expected_symbols = vec![
             Symbol {
                 idx: 0,
-                x: 3,
-                y: 1,
+                pos: Point::new(3, 1),
                 char: '*',
             },
             Symbol {
                 idx: 1,
-                x: 6,
-                y: 3,
+                pos: Point::new(6, 3),
                 char: '#',
             },
             Symbol {
                 idx: 2,
-                x: 3,
-                y: 4,
+                pos: Point::new(3, 4),
                 char: '*',
             },
             Symbol {
                 idx: 3,
-                x: 5,
-                y: 5,
+                pos: Point::new(5, 5),
                 char: '+',
             },
             Symbol {
                 idx: 4,
-                x: 3,
-                y: 8,
+                pos: Point::new(3, 8),
                 char: '$',
             },
             Symbol {
                 idx: 5,
-                x: 5,
-                y: 8,
+                pos: Point::new(5, 8),
                 char: '*',
             },
         ];
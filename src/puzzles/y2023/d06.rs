@@ -1,5 +1,7 @@
 use std::{error::Error, iter::zip, str::FromStr};
 
+use crate::puzzles::math;
+
 pub struct BoatTable {
     times: Vec<u32>,
     distances: Vec<u32>,
@@ -31,14 +33,11 @@ impl FromStr for BoatTable {
 
 impl BoatTable {
     pub fn n_ways_to_win(&self) -> u32 {
-        zip(&self.times, &self.distances)
-            .map(|(t, d)| {
-                let (t_f, d_f) = (f64::from(*t), f64::from(*d));
-                let lower = ((t_f - f64::sqrt(t_f * t_f - 4.0 * d_f)) / 2.0).floor() as u32 + 1;
-                let upper = t - lower;
-                upper - lower + 1
-            })
-            .product()
+        let n_ways = zip(&self.times, &self.distances)
+            .map(|(t, d)| n_ways_to_beat(u64::from(*t), u64::from(*d)))
+            .product::<u64>();
+
+        u32::try_from(n_ways).unwrap()
     }
 }
 
@@ -75,25 +74,29 @@ impl FromStr for BoatRace {
 
 impl BoatRace {
     pub fn n_ways_to_win(&self) -> u64 {
-        // Exponential search past lowest possible button hold
-        let mut max = 1;
-        while max * (self.time - max) <= self.distance {
-            max *= 2;
-        }
-
-        // Binary search for lowest possible button hold
-        let (mut low, mut high) = (0, max);
-
-        while high - low > 1 {
-            let mid = (high - low) / 2 + low;
+        n_ways_to_beat(self.time, self.distance)
+    }
+}
 
-            if mid * (self.time - mid) > self.distance {
-                high = mid;
-            } else {
-                low = mid;
-            }
+/// The number of button holds that go further than `distance`. Holding h goes
+/// h * (time - h), so these are the integers strictly between the roots of
+/// h * h - time * h + distance.
+fn n_ways_to_beat(time: u64, distance: u64) -> u64 {
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+    let beats = |hold: u64| hold * (time - hold) > distance;
+
+    // The integer square root rounds down, so this is the lowest hold that
+    // wins, or one less.
+    let mut lowest = (time - math::isqrt(discriminant)) / 2;
+    while !beats(lowest) {
+        lowest += 1;
+        if lowest > time / 2 {
+            return 0;
         }
-
-        self.time - 2 * high + 1
     }
+
+    // The holds that win are symmetric around time / 2
+    time - 2 * lowest + 1
 }
@@ -0,0 +1,206 @@
+use std::{error::Error, str::FromStr};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::puzzles::{geom::Turn, math::Congruence};
+
+pub struct Network {
+    instructions: Vec<Turn>,
+    edges: FxHashMap<Node, (Node, Node)>,
+}
+
+impl Network {
+    pub fn n_steps_from_to_single(&self, from: &str, to: &str) -> u32 {
+        let from_node: Node = from.parse().unwrap();
+        let to_node: Node = to.parse().unwrap();
+
+        let mut node = from_node;
+        let mut steps = 0;
+        let mut turns = self.instructions.iter().cycle();
+        while node != to_node {
+            let turn = turns.next().unwrap();
+            node = match turn {
+                Turn::Left => self.edges[&node].0.clone(),
+                Turn::Right => self.edges[&node].1.clone(),
+            };
+            steps += 1;
+        }
+
+        steps
+    }
+
+    pub fn n_steps_all_a_to_all_z(&self) -> u64 {
+        let a_nodes: Vec<Node> = self
+            .edges
+            .keys()
+            .filter(|n| n.0[2] == 'A')
+            .cloned()
+            .collect();
+        let z_nodes: FxHashSet<Node> = self
+            .edges
+            .keys()
+            .filter(|n| n.0[2] == 'Z')
+            .cloned()
+            .collect();
+
+        // Every ghost ends up walking in a cycle, since there are only so many
+        // combinations of a node and a position in the instructions. It can be
+        // on a Z-node before its cycle starts and at some steps of its cycle.
+        // In (apparently) every input, a ghost is on a Z-node once per cycle,
+        // at a multiple of its period, but that isn't assumed here.
+        let visits: Vec<ZVisits> = a_nodes.iter().map(|n| self.z_visits(n, &z_nodes)).collect();
+
+        // All ghosts are on a Z-node before some of them started cycling...
+        let before_cycles = visits
+            .iter()
+            .flat_map(|v| &v.before_cycle)
+            .copied()
+            .filter(|&steps| visits.iter().all(|v| v.contains(steps)))
+            .min();
+
+        // ...or once all of them are cycling, at steps that are in one of the
+        // congruences of every ghost.
+        let cycles_start = visits.iter().map(|v| v.cycle_start).max().unwrap();
+        let in_cycles = visits
+            .iter()
+            .fold(vec![Congruence::new(0, 1)], |combined, v| {
+                combined
+                    .iter()
+                    .flat_map(|c| v.in_cycle.iter().filter_map(|vc| c.intersect(vc)))
+                    .collect()
+            })
+            .into_iter()
+            .map(|c| c.first_from(cycles_start))
+            .min();
+
+        let steps = before_cycles
+            .into_iter()
+            .chain(in_cycles)
+            .min()
+            .expect("ghosts should all be on a Z-node at some point");
+        u64::try_from(steps).unwrap()
+    }
+
+    fn z_visits(&self, from: &Node, to: &FxHashSet<Node>) -> ZVisits {
+        // Walk over indices of the nodes, so that a step is a lookup in a Vec.
+        let nodes: Vec<&Node> = self.edges.keys().collect();
+        let indexes: FxHashMap<&Node, usize> =
+            nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let next: Vec<(usize, usize)> = nodes
+            .iter()
+            .map(|n| {
+                let (left, right) = &self.edges[*n];
+                (indexes[left], indexes[right])
+            })
+            .collect();
+        let is_z: Vec<bool> = nodes.iter().map(|n| to.contains(*n)).collect();
+
+        // A ghost is in the same state when it is on the same node at the same
+        // position in the instructions. Record the step at which each state is
+        // first seen, and the Z-nodes visited on the way, until one repeats.
+        let n_instructions = self.instructions.len();
+        let mut first_seen = vec![None; nodes.len() * n_instructions];
+        let mut z_steps = Vec::new();
+        let (mut node, mut idx) = (indexes[from], 0);
+        let mut steps = 0;
+        let cycle_start = loop {
+            let state = &mut first_seen[node * n_instructions + idx];
+            if let Some(cycle_start) = *state {
+                break cycle_start;
+            }
+            *state = Some(steps);
+            if is_z[node] {
+                z_steps.push(steps);
+            }
+
+            node = match self.instructions[idx] {
+                Turn::Left => next[node].0,
+                Turn::Right => next[node].1,
+            };
+            idx = (idx + 1) % n_instructions;
+            steps += 1;
+        };
+
+        let period = steps - cycle_start;
+        let (before_cycle, in_cycle): (Vec<_>, Vec<_>) =
+            z_steps.into_iter().partition(|&s| s < cycle_start);
+        ZVisits {
+            before_cycle,
+            cycle_start,
+            in_cycle: in_cycle
+                .into_iter()
+                .map(|s| Congruence::new(s, period))
+                .collect(),
+        }
+    }
+}
+
+/// The steps at which a ghost is on a Z-node.
+struct ZVisits {
+    before_cycle: Vec<i64>,
+    cycle_start: i64,
+    in_cycle: Vec<Congruence>,
+}
+
+impl ZVisits {
+    fn contains(&self, steps: i64) -> bool {
+        if steps < self.cycle_start {
+            self.before_cycle.contains(&steps)
+        } else {
+            self.in_cycle.iter().any(|c| c.contains(steps))
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let instructions = lines
+            .next()
+            .ok_or("No first line in input")?
+            .chars()
+            .map(Turn::from_char)
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Invalid turn in input")?;
+
+        let mut edges = FxHashMap::default();
+        for line in lines.skip(1) {
+            let (from_str, to_str) = line.split_once(" = ").ok_or("Invalid syntax")?;
+
+            // Parse 1 "from" node
+            let from_node: Node = from_str.parse()?;
+
+            // Remove brackets and parse 2 "to" nodes
+            let to_str = to_str.get(1..(to_str.len() - 1)).ok_or("Invalid syntax")?;
+            let (to_str_l, to_str_r) = to_str.split_once(", ").ok_or("Invalid syntax")?;
+            let to_node_l = to_str_l.parse()?;
+            let to_node_r = to_str_r.parse()?;
+
+            // Add to edges
+            edges.insert(from_node, (to_node_l, to_node_r));
+        }
+
+        Ok(Network {
+            instructions,
+            edges,
+        })
+    }
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct Node([char; 3]);
+
+impl FromStr for Node {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 3 {
+            Err("Node should have 3 characters".into())
+        } else {
+            let chars = [0, 1, 2].map(|i| s.chars().nth(i).unwrap());
+            Ok(Node(chars))
+        }
+    }
+}
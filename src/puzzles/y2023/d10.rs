@@ -1,11 +1,11 @@
 use std::{error::Error, str::FromStr};
 
-use ndarray::{Array, Array2};
+use crate::puzzles::grid::Grid;
 
 use self::tile_set::TileSet;
 
 pub struct Maze {
-    tiles: Array2<char>,
+    tiles: Grid<char>,
     s_pos: (usize, usize),
 }
 
@@ -13,20 +13,13 @@ impl FromStr for Maze {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Assume square
-        let n = s.find('\n').ok_or("No newline")?;
-        let mut s_pos = (0, 0);
-        let tiles = Array::from_iter(s.chars().filter(|c| !c.is_whitespace()).enumerate().map(
-            |(i, c)| {
-                if c == 'S' {
-                    s_pos = (i % n, i / n);
-                }
-                c
-            },
-        ))
-        .into_shape((n, n))?;
+        let tiles = Grid::parse_with(s, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+        let (y, x) = tiles.position(|&c| c == 'S').ok_or("No start tile")?;
 
-        Ok(Maze { tiles, s_pos })
+        Ok(Maze {
+            tiles,
+            s_pos: (x, y),
+        })
     }
 }
 
@@ -43,10 +36,9 @@ impl Maze {
         // is outside, other components are inside.
 
         let enlarged = self.cleaned().enlarged();
-        let n = enlarged.tiles.shape()[0];
         let mut n_enclosed = 0;
 
-        let mut unvisited_tiles = TileSet::new((n, n));
+        let mut unvisited_tiles = TileSet::new(enlarged.tiles.dim());
         for (pos, c) in enlarged.tiles.indexed_iter() {
             if *c == '.' || *c == '*' {
                 unvisited_tiles.push(pos);
@@ -59,15 +51,8 @@ impl Maze {
             let mut inside = true; // Whether this component is inside or outside
             let mut n_dots = 0; // Number of '.' tiles in this component
 
-            while let Some((y, x)) = frontier.pop() {
-                let (y, x) = (y as i32, x as i32);
-                let neighbours = [(y - 1, x), (y + 1, x), (y, x + 1), (y, x - 1)];
-                for pos in neighbours {
-                    if !enlarged.pos_is_in_field(pos) {
-                        continue;
-                    }
-                    let pos = (pos.0 as usize, pos.1 as usize);
-
+            while let Some(tile) = frontier.pop() {
+                for pos in enlarged.tiles.neighbours(tile) {
                     if unvisited_tiles.contains(&pos) && ".*".contains(enlarged.tiles[pos]) {
                         frontier.push(pos);
                         unvisited_tiles.remove(&pos);
@@ -75,7 +60,7 @@ impl Maze {
                         if enlarged.tiles[pos] == '.' {
                             n_dots += 1;
                         }
-                        if enlarged.pos_is_on_edge(pos) {
+                        if enlarged.tiles.is_on_edge(pos) {
                             inside = false
                         }
                     }
@@ -90,23 +75,12 @@ impl Maze {
         n_enclosed
     }
 
-    fn pos_is_in_field(&self, pos: (i32, i32)) -> bool {
-        let n = self.tiles.shape()[0] as i32;
-        pos.0 >= 0 && pos.1 >= 0 && pos.0 < n && pos.1 < n
-    }
-
-    fn pos_is_on_edge(&self, pos: (usize, usize)) -> bool {
-        let n = self.tiles.shape()[0];
-        pos.0 == 0 || pos.1 == 0 || pos.0 == n - 1 || pos.1 == n - 1
-    }
-
     fn iter_loop(&self) -> MazeIter<'_> {
         MazeIter::new(self)
     }
 
     fn cleaned(&self) -> Self {
-        let shape = self.tiles.raw_dim();
-        let mut cleaned = Array::from_elem(shape, '.');
+        let mut cleaned = Grid::from_elem(self.tiles.dim(), '.');
 
         for (pos, tile) in self.iter_loop() {
             cleaned[pos] = tile
@@ -123,8 +97,8 @@ impl Maze {
         // F becomes
         // F-
         // |*
-        let n = self.tiles.shape()[0];
-        let mut enlarged = Array2::from_elem((n * 2, n * 2), '*');
+        let (height, width) = self.tiles.dim();
+        let mut enlarged = Grid::from_elem((height * 2, width * 2), '*');
 
         for ((y, x), c) in self.tiles.indexed_iter() {
             match c {
@@ -157,10 +131,10 @@ impl Maze {
                 }
                 'S' => {
                     enlarged[(y * 2, x * 2)] = 'S';
-                    if x < n - 1 && "-J7".contains(self.tiles[(y, x + 1)]) {
+                    if x < width - 1 && "-J7".contains(self.tiles[(y, x + 1)]) {
                         enlarged[(y * 2, x * 2 + 1)] = '-';
                     }
-                    if y < n - 1 && "|LJ".contains(self.tiles[(y + 1, x)]) {
+                    if y < height - 1 && "|LJ".contains(self.tiles[(y + 1, x)]) {
                         enlarged[(y * 2 + 1, x * 2)] = '|';
                     }
                     if x > 0 && "-LF".contains(self.tiles[(y, x - 1)]) {
@@ -191,7 +165,7 @@ struct MazeIter<'a> {
 
 impl<'a> MazeIter<'a> {
     fn new(maze: &'a Maze) -> Self {
-        let n = maze.tiles.shape()[0];
+        let (height, width) = maze.tiles.dim();
         let (x, y) = maze.s_pos;
 
         // Determine starting direction: pick any valid direction. If can go up,
@@ -200,10 +174,10 @@ impl<'a> MazeIter<'a> {
         if y > 0 && "|7F".contains(maze.tiles[(y - 1, x)]) {
             dx = 0;
             dy = -1;
-        } else if x < n - 1 && "-J7".contains(maze.tiles[(y, x + 1)]) {
+        } else if x < width - 1 && "-J7".contains(maze.tiles[(y, x + 1)]) {
             dx = 1;
             dy = 0;
-        } else if y < n - 1 && "|LJ".contains(maze.tiles[(y + 1, x)]) {
+        } else if y < height - 1 && "|LJ".contains(maze.tiles[(y + 1, x)]) {
             dx = 0;
             dy = 1;
         } else {
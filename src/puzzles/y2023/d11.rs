@@ -60,7 +60,7 @@ impl Galaxies {
         let mut last = coords[0];
         for num in coords {
             let step = *num - last;
-            shift += i64::max(step - 1, 0) * (i64::try_from(multiplier).unwrap() - 1);
+            shift += i64::max(step - 1, 0) * (i64::from(multiplier) - 1);
             last = *num;
             *num += shift;
         }
@@ -69,13 +69,13 @@ impl Galaxies {
 
 #[cfg(test)]
 mod tests {
-    use crate::{template::read_file, Day};
+    use crate::{day, template::read_file, year, PuzzleId};
 
     use super::*;
 
     #[test]
     fn test_expansion_multipliers() {
-        let input = &read_file("examples", Day::new(11).unwrap());
+        let input = &read_file("examples", PuzzleId::new(year!(2023), day!(11))).unwrap();
 
         let mut galaxies: Galaxies = input.parse().unwrap();
         galaxies.expand(10);
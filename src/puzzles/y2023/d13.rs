@@ -1,6 +1,6 @@
 use std::{error::Error, iter::zip, str::FromStr};
 
-use ndarray::{s, Array, Array2};
+use crate::puzzles::grid::Grid;
 
 pub struct Valley {
     patterns: Vec<Pattern>,
@@ -30,17 +30,14 @@ impl Valley {
 }
 
 struct Pattern {
-    grid: Array2<u8>,
+    grid: Grid<u8>,
 }
 
 impl FromStr for Pattern {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.find('\n').unwrap_or(s.len());
-        let height = s.lines().count();
-        let grid =
-            Array::from_iter(s.bytes().filter(|&b| b != b'\n')).into_shape((height, width))?;
+        let grid = Grid::parse_with(s, |c| "#.".contains(c).then_some(c as u8))?;
 
         Ok(Pattern { grid })
     }
@@ -48,8 +45,8 @@ impl FromStr for Pattern {
 
 impl Pattern {
     fn symmetry_score(&self) -> u32 {
-        let height = self.grid.shape()[0];
-        let width = self.grid.shape()[1];
+        let height = self.grid.height();
+        let width = self.grid.width();
 
         for row_idx in 0..(height - 1) {
             if self.has_horizontal_symmetry_at(row_idx) {
@@ -67,8 +64,8 @@ impl Pattern {
     }
 
     fn fixed_symmetry_score(&self) -> u32 {
-        let height = self.grid.shape()[0];
-        let width = self.grid.shape()[1];
+        let height = self.grid.height();
+        let width = self.grid.width();
 
         for row_idx in 0..(height - 1) {
             if self.almost_has_horizontal_symmetry_at(row_idx) {
@@ -86,11 +83,11 @@ impl Pattern {
     }
 
     fn has_horizontal_symmetry_at(&self, row_idx: usize) -> bool {
-        let height = self.grid.shape()[0];
+        let height = self.grid.height();
 
         for delta in 0..usize::min(row_idx + 1, height - row_idx - 1) {
-            let row_before = self.grid.slice(s![row_idx - delta, ..]);
-            let row_after = self.grid.slice(s![row_idx + delta + 1, ..]);
+            let row_before = self.grid.row(row_idx - delta);
+            let row_after = self.grid.row(row_idx + delta + 1);
             if row_before != row_after {
                 return false;
             }
@@ -100,12 +97,12 @@ impl Pattern {
     }
 
     fn almost_has_horizontal_symmetry_at(&self, row_idx: usize) -> bool {
-        let height = self.grid.shape()[0];
+        let height = self.grid.height();
 
         let mut diff = 0;
         for delta in 0..usize::min(row_idx + 1, height - row_idx - 1) {
-            let row_before = self.grid.slice(s![row_idx - delta, ..]);
-            let row_after = self.grid.slice(s![row_idx + delta + 1, ..]);
+            let row_before = self.grid.row(row_idx - delta);
+            let row_after = self.grid.row(row_idx + delta + 1);
             diff += zip(row_before, row_after).filter(|(b, a)| b != a).count();
 
             if diff > 1 {
@@ -117,11 +114,11 @@ impl Pattern {
     }
 
     fn has_vertical_symmetry_at(&self, col_idx: usize) -> bool {
-        let width = self.grid.shape()[1];
+        let width = self.grid.width();
 
         for delta in 0..usize::min(col_idx + 1, width - col_idx - 1) {
-            let col_before = self.grid.slice(s![.., col_idx - delta]);
-            let col_after = self.grid.slice(s![.., col_idx + delta + 1]);
+            let col_before = self.grid.column(col_idx - delta);
+            let col_after = self.grid.column(col_idx + delta + 1);
             if col_before != col_after {
                 return false;
             }
@@ -131,12 +128,12 @@ impl Pattern {
     }
 
     fn almost_has_vertical_symmetry_at(&self, col_idx: usize) -> bool {
-        let width = self.grid.shape()[1];
+        let width = self.grid.width();
 
         let mut diff = 0;
         for delta in 0..usize::min(col_idx + 1, width - col_idx - 1) {
-            let col_before = self.grid.slice(s![.., col_idx - delta]);
-            let col_after = self.grid.slice(s![.., col_idx + delta + 1]);
+            let col_before = self.grid.column(col_idx - delta);
+            let col_after = self.grid.column(col_idx + delta + 1);
             diff += zip(col_before, col_after).filter(|(b, a)| b != a).count();
 
             if diff > 1 {
@@ -1,20 +1,17 @@
 use std::{error::Error, str::FromStr};
 
-use ndarray::{Array, Array2};
-use rustc_hash::FxHashMap;
+use crate::puzzles::{cycles, grid::Grid};
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct Platform {
-    grid: Array2<u8>,
+    grid: Grid<u8>,
 }
 
 impl FromStr for Platform {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.find('\n').unwrap_or(s.len());
-        let height = s.lines().count();
-        let grid =
-            Array::from_iter(s.bytes().filter(|&b| b != b'\n')).into_shape((height, width))?;
+        let grid = Grid::parse_with(s, |c| "#O.".contains(c).then_some(c as u8))?;
 
         Ok(Platform { grid })
     }
@@ -94,39 +91,22 @@ impl Platform {
     }
 
     pub fn spin(&mut self, n_iter: usize) {
-        let mut history: FxHashMap<Array2<u8>, usize> = FxHashMap::default();
-
-        for i in 0..n_iter {
-            self.slide_north();
-            self.slide_west();
-            self.slide_south();
-            self.slide_east();
-
-            if let Some(n) = history.get(&self.grid) {
-                // It will repeat itself every i - n cycles.
-                let period = i - n;
-                let value_final = (n_iter - 1 - n) % period + n;
-
-                for (key, &value) in &history {
-                    if value == value_final {
-                        self.grid = key.clone();
-                        return;
-                    }
-                }
-            }
-
-            history.insert(self.grid.clone(), i);
-        }
+        // The platform ends up repeating itself, so the cycles in between can
+        // be skipped.
+        *self = cycles::advance(self.clone(), n_iter, |platform| {
+            platform.slide_north();
+            platform.slide_west();
+            platform.slide_south();
+            platform.slide_east();
+        });
     }
 
     pub fn total_load(&self) -> u32 {
-        let height = self.grid.shape()[0];
-        let width = self.grid.shape()[1];
+        let height = self.grid.height();
 
         let mut load = 0;
-        for (idx, &elem) in self.grid.iter().enumerate() {
+        for ((row, _), &elem) in self.grid.indexed_iter() {
             if elem == b'O' {
-                let row = idx / width;
                 load += height - row
             }
         }
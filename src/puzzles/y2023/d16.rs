@@ -0,0 +1,149 @@
+use std::{error::Error, str::FromStr};
+
+use ndarray::Array2;
+
+use crate::puzzles::{
+    geom::{Direction, Point},
+    grid::Grid,
+};
+
+pub struct MirrorGrid {
+    grid: Grid<u8>,
+}
+
+impl FromStr for MirrorGrid {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |c| "./\\|-".contains(c).then_some(c as u8))?;
+
+        Ok(MirrorGrid { grid })
+    }
+}
+
+impl MirrorGrid {
+    pub fn follow_beam(&self, entry_pos: Point<isize>, entry_direction: Direction) -> BeamPath {
+        let mut path = Array2::from_elem(self.grid.raw_dim(), 0);
+        let mut beam_heads = vec![(entry_pos, entry_direction)];
+
+        while let Some((head_pos, head_direction)) = beam_heads.pop() {
+            // The currently examined beam is about to enter head_pos, traveling
+            // in direction head_direction.
+
+            // Skip if leaving grid
+            let Some(head_pos_u) = head_pos
+                .grid_pos()
+                .filter(|&pos| self.grid.get_pos(pos).is_some())
+            else {
+                continue;
+            };
+
+            // If it has already entered this position in this direction before,
+            // it will continue along a path that we have already followed
+            // before, so we can quit. Otherwise, we mark it in the bitfield.
+            let direction_bit = 1 << head_direction as u8;
+            if path[head_pos_u] & direction_bit > 0 {
+                continue;
+            }
+            path[head_pos_u] |= direction_bit;
+
+            self.follow_beam_step(
+                head_pos,
+                head_direction,
+                self.grid[head_pos_u],
+                &mut beam_heads,
+            )
+        }
+
+        BeamPath(path)
+    }
+
+    pub fn find_max_energized_tiles(&self) -> u32 {
+        let (height, width) = (
+            isize::try_from(self.grid.height()).unwrap(),
+            isize::try_from(self.grid.width()).unwrap(),
+        );
+        let mut max = 0;
+
+        // Top edge
+        for x in 0..width {
+            let n_energized = self
+                .follow_beam(Point::new(x, 0), Direction::South)
+                .num_energized();
+            max = u32::max(max, n_energized);
+        }
+
+        // Right edge
+        for y in 0..height {
+            let n_energized = self
+                .follow_beam(Point::new(width - 1, y), Direction::West)
+                .num_energized();
+            max = u32::max(max, n_energized);
+        }
+
+        // Bottom edge
+        for x in 0..width {
+            let n_energized = self
+                .follow_beam(Point::new(x, height - 1), Direction::North)
+                .num_energized();
+            max = u32::max(max, n_energized);
+        }
+
+        // Left edge
+        for y in 0..height {
+            let n_energized = self
+                .follow_beam(Point::new(0, y), Direction::East)
+                .num_energized();
+            max = u32::max(max, n_energized);
+        }
+
+        max
+    }
+
+    fn follow_beam_step(
+        &self,
+        head_pos: Point<isize>,
+        head_direction: Direction,
+        element: u8,
+        beam_heads: &mut Vec<(Point<isize>, Direction)>,
+    ) {
+        let mut continue_in =
+            |direction: Direction| beam_heads.push((head_pos + direction.offset(), direction));
+        let is_vertical = head_direction.is_vertical();
+
+        match element {
+            b'.' => continue_in(head_direction),
+
+            // '/' turns a vertical beam right and a horizontal beam left, '\'
+            // does the opposite.
+            b'/' if is_vertical => continue_in(head_direction.turn_right()),
+            b'/' => continue_in(head_direction.turn_left()),
+            b'\\' if is_vertical => continue_in(head_direction.turn_left()),
+            b'\\' => continue_in(head_direction.turn_right()),
+
+            // A splitter passes a beam along it, and splits a beam across it
+            // into both directions along it.
+            b'|' if !is_vertical => {
+                continue_in(Direction::North);
+                continue_in(Direction::South);
+            }
+            b'-' if is_vertical => {
+                continue_in(Direction::East);
+                continue_in(Direction::West);
+            }
+            b'|' | b'-' => continue_in(head_direction),
+
+            _ => panic!("Invalid character"),
+        };
+    }
+}
+
+// Each byte is a bitfield with the bit for each direction set if a beam enters
+// it in the corresponding direction.
+pub struct BeamPath(Array2<u8>);
+
+impl BeamPath {
+    pub fn num_energized(&self) -> u32 {
+        u32::try_from(self.0.iter().filter(|&&x| x > 0).count()).unwrap()
+    }
+}
@@ -0,0 +1,231 @@
+use std::{error::Error, str::FromStr};
+
+use crate::puzzles::{geom::Direction, graph, grid::Grid};
+
+#[derive(Eq, PartialEq)]
+pub struct Map {
+    grid: Grid<u8>,
+
+    /// Shortest path from position to exit, taking into account heat loss but
+    /// no "consecutive steps" constraints.
+    heur_cost_to_target: Grid<u32>,
+}
+
+impl FromStr for Map {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |c| c.to_digit(10).map(|d| d as u8))?;
+        let heur_cost_to_target = Self::find_lowest_cost_to_target(&grid);
+
+        Ok(Map {
+            grid,
+            heur_cost_to_target,
+        })
+    }
+}
+
+impl Map {
+    pub fn cheapest_path_cost_normal(&self) -> Option<u32> {
+        self.cheapest_path_cost::<Crucible>()
+    }
+
+    pub fn cheapest_path_cost_ultra(&self) -> Option<u32> {
+        self.cheapest_path_cost::<UltraCrucible>()
+    }
+
+    /// Find the cost of the shortest path using the A* algorithm
+    fn cheapest_path_cost<T: Node>(&self) -> Option<u32> {
+        let _span = crate::span!("search");
+
+        let graph = graph::from_fn(|node: &T| {
+            node.get_all_neighbours(self)
+                .into_iter()
+                .flatten()
+                .map(|neighbour| {
+                    let cost = u32::from(self.grid[neighbour.pos()]);
+                    (neighbour, cost)
+                })
+        });
+
+        // start direction South disallows turning back North, but that is
+        // ok because that would take us off the map.
+        let start_node = T::new((0, 0), Direction::South);
+
+        let path = graph::astar(
+            &graph,
+            start_node,
+            |node| node.heuristic(self),
+            |node| node.pos() == self.target() && node.can_stop(),
+        )?;
+        Some(path.cost)
+    }
+
+    fn target(&self) -> (usize, usize) {
+        (self.grid.height() - 1, self.grid.width() - 1)
+    }
+
+    fn get_neighbour_pos(
+        &self,
+        pos: (usize, usize),
+        direction: Direction,
+    ) -> Option<(usize, usize)> {
+        self.grid.offset(pos, direction.grid_offset())
+    }
+
+    /// Find the shortest path from any position to the target position using
+    /// Dijkstra's algorithm. This takes into account heat loss but no
+    /// "consecutive steps" constraints.
+    fn find_lowest_cost_to_target(grid: &Grid<u8>) -> Grid<u32> {
+        let _span = crate::span!("heuristic");
+
+        // Walk back from the target, paying for every position left rather
+        // than entered.
+        let target = (grid.height() - 1, grid.width() - 1);
+        let graph = graph::from_fn(|&pos: &(usize, usize)| {
+            grid.neighbours(pos)
+                .map(move |neighbour| (neighbour, u32::from(grid[pos])))
+        });
+
+        let mut lowest_cost = Grid::from_elem(grid.dim(), u32::MAX);
+        for (pos, cost) in graph::dijkstra_all(&graph, target) {
+            lowest_cost[pos] = cost;
+        }
+
+        lowest_cost
+    }
+}
+
+trait Node: Clone + Eq + std::hash::Hash {
+    fn new(start_pos: (usize, usize), start_direction: Direction) -> Self;
+    fn pos(&self) -> (usize, usize);
+    fn can_stop(&self) -> bool;
+    fn make_step(&self, map: &Map, direction: Direction) -> Option<Self>;
+    fn heuristic(&self, map: &Map) -> u32;
+
+    fn get_all_neighbours(&self, map: &Map) -> [Option<Self>; 4] {
+        Direction::ALL.map(|direction| self.make_step(map, direction))
+    }
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct Crucible {
+    pos: (usize, usize),
+    direction: Direction,
+    remaining_steps: u8, // How many more steps are allowed in direction
+}
+
+impl Node for Crucible {
+    fn new(start_pos: (usize, usize), start_direction: Direction) -> Self {
+        Crucible {
+            pos: start_pos,
+            direction: start_direction,
+            remaining_steps: 3,
+        }
+    }
+
+    fn pos(&self) -> (usize, usize) {
+        self.pos
+    }
+
+    fn can_stop(&self) -> bool {
+        true
+    }
+
+    fn make_step(&self, map: &Map, direction: Direction) -> Option<Self> {
+        // neighbour is on map
+        let neighbour_pos = map.get_neighbour_pos(self.pos(), direction)?;
+
+        // turn is allowed (not 180)
+        if self.direction == direction.opposite() {
+            return None;
+        }
+
+        // don't exceed remaining steps
+        if self.direction == direction && self.remaining_steps == 0 {
+            return None;
+        }
+
+        // update remaining steps
+        let remaining_steps = if self.direction == direction {
+            self.remaining_steps - 1
+        } else {
+            2
+        };
+
+        Some(Crucible {
+            pos: neighbour_pos,
+            direction,
+            remaining_steps,
+        })
+    }
+
+    fn heuristic(&self, map: &Map) -> u32 {
+        map.heur_cost_to_target[self.pos]
+    }
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct UltraCrucible {
+    pos: (usize, usize),
+    direction: Direction,
+    consecutive_steps: u8, // how many consecutive steps in direction it already performed
+}
+
+impl Node for UltraCrucible {
+    fn new(start_pos: (usize, usize), start_direction: Direction) -> Self {
+        UltraCrucible {
+            pos: start_pos,
+            direction: start_direction,
+            consecutive_steps: 0,
+        }
+    }
+
+    fn pos(&self) -> (usize, usize) {
+        self.pos
+    }
+
+    fn can_stop(&self) -> bool {
+        self.consecutive_steps >= 4
+    }
+
+    fn make_step(&self, map: &Map, direction: Direction) -> Option<Self> {
+        // neighbour is on map
+        let neighbour_pos = map.get_neighbour_pos(self.pos(), direction)?;
+
+        // turn is allowed (not 180)
+        if self.direction == direction.opposite() {
+            return None;
+        }
+
+        // satisfy consecutive steps constraint. Special case: starting node can
+        // always turn even if it has not made 4 steps yet.
+        if self.direction == direction
+            && self.consecutive_steps >= 10
+            && self.consecutive_steps != 0
+        {
+            return None;
+        }
+        if self.direction != direction && self.consecutive_steps < 4 && self.consecutive_steps != 0
+        {
+            return None;
+        }
+
+        // update consecutive steps
+        let consecutive_steps = if self.direction == direction {
+            self.consecutive_steps + 1
+        } else {
+            1
+        };
+
+        Some(UltraCrucible {
+            pos: neighbour_pos,
+            direction,
+            consecutive_steps,
+        })
+    }
+
+    fn heuristic(&self, map: &Map) -> u32 {
+        map.heur_cost_to_target[self.pos]
+    }
+}
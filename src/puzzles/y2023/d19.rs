@@ -317,7 +317,7 @@ impl FromStr for WorkflowRule {
             action_str = &s[(pos + 1)..];
         } else {
             condition = None;
-            action_str = &s;
+            action_str = s;
         }
         let action = action_str.parse()?;
 
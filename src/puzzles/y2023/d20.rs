@@ -1,8 +1,13 @@
 use std::{collections::VecDeque, error::Error, str::FromStr};
 
-use num_integer::lcm;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
+use crate::puzzles::{
+    cycles, graph,
+    math::{self, Congruence},
+};
+
+#[derive(Clone)]
 pub struct ModuleNetwork {
     modules: Vec<Module>,
     input_idx: usize,
@@ -117,33 +122,105 @@ impl ModuleNetwork {
         (n_low_total, n_high_total)
     }
 
-    pub fn steps_until_rx_first_low(&mut self) -> u64 {
+    pub fn steps_until_rx_first_low(&self) -> u64 {
         // Through looking a the graph in graphviz, it can be determined that rx
         // first goes low when the conjunctions bl, mr, pv and vv first go low
         // together, which is when ks, kb, sx and jt first go high together.
         // Because the subgraphs corresponding to bl, mr, pv and vv aren't
         // sending pulses to each other and because these subgraphs are behaving
-        // periodically, we can take the lcm of the steps required for ks, kb,
-        // sx and jt seperately.
+        // periodically, we can determine the steps required for ks, kb, sx and
+        // jt seperately and combine them with the Chinese Remainder Theorem.
+        // This assumes that they receive a low pulse once per period.
 
         // This could be optimized by determining ks, kb, sx and jt in one go
         // without resetting in between.
-        let steps_ks = self.press_until_first_low_received("ks");
-        self.reset();
+        let mut first_steps = Vec::new();
+        let mut congruences = Vec::new();
+        for module_name in ["ks", "kb", "sx", "jt"] {
+            // It goes low again every period, if its subgraph had started
+            // repeating by then.
+            let (steps, cycle) = self.first_low_and_cycle_of_branch_to(module_name);
+            let steps = i64::try_from(steps).unwrap();
+            assert!(
+                i64::try_from(cycle.prefix_len).is_ok_and(|prefix_len| prefix_len <= steps),
+                "{module_name} should first go low once its subgraph repeats"
+            );
+            first_steps.push(steps);
+            congruences.push(Congruence::new(steps, i64::try_from(cycle.period).unwrap()));
+        }
 
-        let steps_kb = self.press_until_first_low_received("kb");
-        self.reset();
+        let all_low = math::crt(congruences).expect("ks, kb, sx and jt should go low together");
+        let steps = all_low.first_from(first_steps.into_iter().max().unwrap());
+        u64::try_from(steps).unwrap()
+    }
 
-        let steps_sx = self.press_until_first_low_received("sx");
-        self.reset();
+    /// The number of presses until the module first receives a low pulse, and
+    /// the cycle of the states of the modules between the broadcaster and the
+    /// module, when pressing the button from the reset state.
+    fn first_low_and_cycle_of_branch_to(&self, module_name: &str) -> (u64, cycles::Cycle) {
+        // Press the button on a copy of the network in which the broadcaster
+        // only sends to the modules from which module_name can be reached.
+        let module_idx = self.indexes[module_name];
+        let connections = graph::unweighted(|idx: &usize| {
+            self.outputs
+                .get(idx)
+                .into_iter()
+                .flatten()
+                .map(|&(idx_module, _)| idx_module)
+        });
+        let mut branch = self.clone();
+        branch.reset();
+        branch
+            .outputs
+            .get_mut(&self.input_idx)
+            .unwrap()
+            .retain(|&(idx_module, _)| {
+                graph::bfs_distances(&connections, idx_module, usize::MAX).contains_key(&module_idx)
+            });
+
+        // The modules hold all state that is left after a press. Remember
+        // after how many presses each state was first seen, until one repeats.
+        let mut first_seen = HashMap::default();
+        let mut first_low = None;
+        for n_presses in 0.. {
+            let prefix_len = *first_seen.entry(branch.packed_state()).or_insert(n_presses);
+            if prefix_len < n_presses {
+                let first_low = first_low.expect("the module should receive a low pulse");
+                let period = n_presses - prefix_len;
+                return (first_low, cycles::Cycle { prefix_len, period });
+            }
 
-        let steps_jt = self.press_until_first_low_received("jt");
-        self.reset();
+            branch.press_button();
+            while !branch.queue.is_empty() {
+                let pulse = branch.step();
+                if pulse.idx_rx_module == module_idx && !pulse.high && first_low.is_none() {
+                    first_low = Some(u64::try_from(n_presses + 1).unwrap());
+                }
+            }
+        }
+        unreachable!("a sequence of usize::MAX presses without a repeat")
+    }
 
-        [steps_ks, steps_kb, steps_sx, steps_jt]
-            .into_iter()
-            .reduce(lcm)
-            .unwrap()
+    /// The states of all modules, packed into bits.
+    fn packed_state(&self) -> Vec<u64> {
+        let bits = self.modules.iter().flat_map(|module| match module {
+            Module::FlipFlop(state) => std::slice::from_ref(state),
+            Module::Conjuction(mem) => mem.as_slice(),
+            Module::BroadCast | Module::UnTyped => &[],
+        });
+
+        let mut packed = Vec::new();
+        let (mut word, mut n_bits) = (0, 0);
+        for &bit in bits {
+            word |= u64::from(bit) << n_bits;
+            n_bits += 1;
+            if n_bits == 64 {
+                packed.push(word);
+                (word, n_bits) = (0, 0);
+            }
+        }
+        packed.push(word);
+        packed
     }
 
     /// Process 1 pulse from the queue and return it. Panics if the queue is empty.
@@ -195,23 +272,6 @@ impl ModuleNetwork {
         )
     }
 
-    fn press_until_first_low_received(&mut self, module_name: &str) -> u64 {
-        let mut n_presses: u64 = 0;
-        let module_idx = self.indexes[module_name];
-
-        loop {
-            while !self.queue.is_empty() {
-                let pulse = self.step();
-                if pulse.idx_rx_module == module_idx && !pulse.high {
-                    return n_presses;
-                }
-            }
-
-            self.press_button();
-            n_presses += 1;
-        }
-    }
-
     fn reset(&mut self) {
         for module in self.modules.iter_mut() {
             match module {
@@ -227,12 +287,14 @@ impl ModuleNetwork {
     }
 }
 
+#[derive(Clone)]
 struct Pulse {
     idx_rx_module: usize,
     idx_rx_input: usize,
     high: bool,
 }
 
+#[derive(Clone)]
 enum Module {
     BroadCast,
     FlipFlop(bool),
@@ -0,0 +1,164 @@
+use std::{error::Error, str::FromStr};
+
+use crate::puzzles::{
+    cycles, graph,
+    grid::{Grid, DIRECTIONS_4},
+};
+
+pub struct Garden {
+    grid: Grid<u8>,
+    start_pos: (usize, usize),
+}
+
+impl FromStr for Garden {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s, |c| ".#S".contains(c).then_some(c as u8))?;
+        let start_pos = grid.position(|&b| b == b'S').ok_or("No start position")?;
+
+        Ok(Garden { grid, start_pos })
+    }
+}
+
+impl Garden {
+    pub fn num_tiles_reacheable_after(&self, n_steps: u64, with_wrapping: bool) -> u64 {
+        let start_pos = (
+            isize::try_from(self.start_pos.0).unwrap(),
+            isize::try_from(self.start_pos.1).unwrap(),
+        );
+
+        // If a position was first reached after n steps, it will be reached
+        // again after n + 2, n + 4, n + 6, etc.
+        let get_neighbours = if with_wrapping {
+            Self::neighbours_with_wrapping
+        } else {
+            Self::neighbours
+        };
+        let graph = graph::unweighted(|pos| get_neighbours(self, pos));
+        let max_steps = usize::try_from(n_steps).unwrap();
+        let reached_after = graph::bfs_distances(&graph, start_pos, max_steps);
+
+        // if n_steps is even, we can reach all positions we reached after an
+        // even number of steps.
+        u64::try_from(
+            reached_after
+                .into_values()
+                .filter(|&steps| steps % 2 == max_steps % 2)
+                .count(),
+        )
+        .unwrap()
+    }
+
+    /// More efficient version of num_tiles_reacheable_after for large n_steps.
+    /// Only for the wrapping case.
+    pub fn num_tiles_reacheable_extrapolated(&self, n_steps: u64) -> u64 {
+        // In my puzzle input (and I suspect everyones input), there are
+        // straight horizontal and vertical lines without rocks from S to the
+        // edges, allowing the edge to be reached in 65 steps, and the next
+        // repeated garden 131 steps after that. So after the first 65 steps,
+        // the length of the outer perimeter that can be reached will grow
+        // linearly with fixed increments every 131 steps, and therefore the
+        // area inside it will grow quadratically every 131 steps.
+
+        let offset = n_steps % 131;
+        let n_periods = (n_steps - offset) / 131;
+        if n_periods < 3 {
+            return self.num_tiles_reacheable_after(n_steps, true);
+        }
+
+        let first_periods = [offset, offset + 131, offset + 262]
+            .map(|n_steps| i64::try_from(self.num_tiles_reacheable_after(n_steps, true)).unwrap());
+        let total = cycles::extrapolate(&first_periods, i64::try_from(n_periods).unwrap());
+
+        u64::try_from(total).unwrap()
+    }
+
+    fn neighbours_with_wrapping(&self, pos: &(isize, isize)) -> Vec<(isize, isize)> {
+        DIRECTIONS_4
+            .into_iter()
+            .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
+            .filter(|&neighbour_pos| *self.grid.get_wrapping(neighbour_pos) != b'#')
+            .collect()
+    }
+
+    fn neighbours(&self, pos: &(isize, isize)) -> Vec<(isize, isize)> {
+        DIRECTIONS_4
+            .into_iter()
+            .map(|(dy, dx)| (pos.0 + dy, pos.1 + dx))
+            .filter(|&neighbour_pos| {
+                self.grid
+                    .checked_pos(neighbour_pos)
+                    .is_some_and(|pos| self.grid[pos] != b'#')
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{day, year, PuzzleId};
+
+    use super::*;
+
+    #[test]
+    fn test_num_tiles_reacheable_after_wo_wrapping() {
+        let input =
+            crate::template::read_file("examples", PuzzleId::new(year!(2023), day!(21))).unwrap();
+        let garden: Garden = input.parse().unwrap();
+        assert_eq!(garden.num_tiles_reacheable_after(6, false), 16);
+    }
+
+    #[test]
+    fn test_num_tiles_reacheable_after_with_wrapping() {
+        let input =
+            crate::template::read_file("examples", PuzzleId::new(year!(2023), day!(21))).unwrap();
+        let garden: Garden = input.parse().unwrap();
+
+        assert_eq!(garden.num_tiles_reacheable_after(6, true), 16);
+        assert_eq!(garden.num_tiles_reacheable_after(10, true), 50);
+        assert_eq!(garden.num_tiles_reacheable_after(50, true), 1594);
+        assert_eq!(garden.num_tiles_reacheable_after(100, true), 6536);
+        assert_eq!(garden.num_tiles_reacheable_after(500, true), 167004);
+    }
+
+    #[test]
+    fn test_num_tiles_reacheable_extrapolated() {
+        let input =
+            crate::template::read_file("inputs", PuzzleId::new(year!(2023), day!(21))).unwrap();
+        let garden: Garden = input.parse().unwrap();
+
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(6),
+            garden.num_tiles_reacheable_after(6, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(65),
+            garden.num_tiles_reacheable_after(65, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(100),
+            garden.num_tiles_reacheable_after(100, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(131),
+            garden.num_tiles_reacheable_after(131, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(392),
+            garden.num_tiles_reacheable_after(392, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(393),
+            garden.num_tiles_reacheable_after(393, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(394),
+            garden.num_tiles_reacheable_after(394, true)
+        );
+        assert_eq!(
+            garden.num_tiles_reacheable_extrapolated(650),
+            garden.num_tiles_reacheable_after(650, true)
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use rustc_hash::FxHashMap as HashMap;
 use rustc_hash::FxHashSet as HashSet;
-use std::collections::VecDeque;
 use std::hash::Hash;
 use std::{error::Error, str::FromStr};
 
+use crate::puzzles::{geom::Point3, graph};
+
 pub struct BrickPile(Vec<Brick>); // Vector is sorted by bottom z-coordinate ascending
 
 impl FromStr for BrickPile {
@@ -15,7 +16,7 @@ impl FromStr for BrickPile {
             .map(|l| l.parse())
             .collect::<Result<Vec<Brick>, _>>()?;
 
-        bricks.sort_by_key(|b| b.lfb.2);
+        bricks.sort_by_key(|b| b.lfb.z);
         Self::drop_bricks(&mut bricks);
 
         Ok(BrickPile(bricks))
@@ -69,7 +70,7 @@ impl BrickPile {
         // `bricks_argsort_top` contain indices into `bricks`, sorted by the top
         // z-coordinate of the bricks.
         let mut bricks_argsort_top: Vec<usize> = (0..bricks.len()).collect();
-        bricks_argsort_top.sort_by_key(|&i| bricks[i].rbt.2);
+        bricks_argsort_top.sort_by_key(|&i| bricks[i].rbt.z);
 
         for idx in 0..bricks.len() {
             let brick = &bricks[idx];
@@ -77,26 +78,26 @@ impl BrickPile {
 
             // Find new z-coordinate by iterating over all bricks whose tops are below this brick's bottom
             let idx_first_not_below =
-                bricks_argsort_top.partition_point(|&i| bricks[i].rbt.2 < brick.lfb.2);
+                bricks_argsort_top.partition_point(|&i| bricks[i].rbt.z < brick.lfb.z);
             for idx_brick_below in bricks_argsort_top[0..idx_first_not_below].iter().rev() {
                 let brick_below = &bricks[*idx_brick_below];
 
                 if brick.overlaps_x(brick_below) && brick.overlaps_y(brick_below) {
-                    new_z = brick_below.rbt.2 + 1;
+                    new_z = brick_below.rbt.z + 1;
                     break;
                 }
             }
 
             // Set new z-coordinate
             let brick = &mut bricks[idx];
-            brick.rbt.2 -= brick.lfb.2 - new_z;
-            brick.lfb.2 = new_z;
+            brick.rbt.z -= brick.lfb.z - new_z;
+            brick.lfb.z = new_z;
 
             // Reorder bricks_argsort_top to keep them sorted
-            bricks_argsort_top.sort_by_key(|&i| bricks[i].rbt.2);
+            bricks_argsort_top.sort_by_key(|&i| bricks[i].rbt.z);
         }
 
-        bricks.sort_by_key(|brick| brick.lfb.2);
+        bricks.sort_by_key(|brick| brick.lfb.z);
     }
 
     fn find_all_supported_by(&self) -> HashMap<&Brick, Vec<&Brick>> {
@@ -104,7 +105,7 @@ impl BrickPile {
 
         for (idx, brick) in self.0.iter().enumerate() {
             for brick_above in &self.0[(idx + 1)..] {
-                if brick_above.lfb.2 > brick.rbt.2 + 1 {
+                if brick_above.lfb.z > brick.rbt.z + 1 {
                     break; // this brick_above and following cannot be supported by brick
                 }
 
@@ -154,45 +155,20 @@ impl BrickPile {
         &'a self,
         supported_by: &HashMap<&'a Brick, Vec<&'a Brick>>,
     ) -> Vec<&'a Brick> {
-        // using Kahn's algorithm. Assuming acyclic graph.
-        let mut n_unvisited_below: HashMap<&Brick, usize> = self
-            .0
-            .iter()
-            .map(|b| (b, supported_by.get(b).map(|sb| sb.len()).unwrap_or(0)))
-            .collect();
-        let bottom_bricks: Vec<_> = self
-            .0
-            .iter()
-            .filter(|&b| supported_by.get(b).is_none())
-            .collect();
-        let mut queue = VecDeque::from(bottom_bricks);
-        let mut topsort = Vec::with_capacity(self.0.len());
         let supporting = self.find_all_supporting(supported_by);
+        let graph = graph::unweighted(|brick: &&Brick| {
+            supporting.get(brick).into_iter().flatten().copied()
+        });
+        let bottom_bricks = self.0.iter().filter(|&b| supported_by.get(b).is_none());
 
-        while let Some(brick) = queue.pop_front() {
-            topsort.push(brick);
-
-            let Some(above) = supporting.get(brick) else {
-                continue;
-            };
-
-            for brick_above in above {
-                let n = n_unvisited_below.get_mut(brick_above).unwrap();
-                *n -= 1;
-                if *n == 0 {
-                    queue.push_back(brick_above);
-                }
-            }
-        }
-
-        topsort
+        graph::topological_sort(&graph, bottom_bricks).expect("bricks only support bricks above")
     }
 }
 
-#[derive(Clone, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct Brick {
-    lfb: (u32, u32, u32), // Left Front Bottom (x, y, z)
-    rbt: (u32, u32, u32), // Right Back Top (x, y, z)
+    lfb: Point3<u32>, // Left Front Bottom
+    rbt: Point3<u32>, // Right Back Top
 }
 
 impl FromStr for Brick {
@@ -200,49 +176,13 @@ impl FromStr for Brick {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (lfb_str, rbt_str) = s.split_once('~').ok_or::<String>("Missing '~'".into())?;
-        let mut lfb_coords = lfb_str.split(',');
-        let mut rbt_coords = rbt_str.split(',');
-
-        let lfb = (
-            lfb_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-            lfb_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-            lfb_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-        );
-        if lfb_coords.next().is_some() {
-            return Err("Too many coords".into());
-        }
-
-        let rbt = (
-            rbt_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-            rbt_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-            rbt_coords
-                .next()
-                .ok_or::<String>("Missing coord".into())?
-                .parse()?,
-        );
-        if rbt_coords.next().is_some() {
-            return Err("Too many coords".into());
-        }
+        let lfb: Point3<u32> = lfb_str.parse()?;
+        let rbt: Point3<u32> = rbt_str.parse()?;
 
-        if lfb.0 > rbt.0 || lfb.1 > rbt.1 || lfb.2 > rbt.2 {
+        if lfb.x > rbt.x || lfb.y > rbt.y || lfb.z > rbt.z {
             return Err("Left coord may not exceed right coord".into());
         }
-        if lfb.2 < 1 {
+        if lfb.z < 1 {
             return Err("Z-coordinates should be >= 1".into());
         }
 
@@ -252,11 +192,11 @@ impl FromStr for Brick {
 
 impl Brick {
     fn overlaps_x(&self, other: &Brick) -> bool {
-        self.lfb.0 <= other.rbt.0 && other.lfb.0 <= self.rbt.0
+        self.lfb.x <= other.rbt.x && other.lfb.x <= self.rbt.x
     }
 
     fn overlaps_y(&self, other: &Brick) -> bool {
-        self.lfb.1 <= other.rbt.1 && other.lfb.1 <= self.rbt.1
+        self.lfb.y <= other.rbt.y && other.lfb.y <= self.rbt.y
     }
 }
 
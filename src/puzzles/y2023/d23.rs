@@ -1,28 +1,26 @@
-use std::collections::VecDeque;
 use std::{error::Error, str::FromStr};
 
-use ndarray::{Array, Array2};
 use rustc_hash::FxHashMap as HashMap;
-use rustc_hash::FxHashSet as HashSet;
 
-use self::bitmap::BitMap64;
+use crate::puzzles::graph::{self, AdjacencyList};
+use crate::puzzles::grid::{Grid, Pos, DIRECTIONS_4};
 
-type Pos = (usize, usize);
+use self::bitmap::BitMap64;
 
 pub struct Map {
-    grid: Grid,
+    grid: TrailMap,
     vertex2idx: HashMap<Pos, u32>,
     idx2vertex: HashMap<u32, Pos>,
-    edges_out: HashMap<u32, Vec<(u32, u32)>>, // key: idx_from, value: pairs of (idx_to, distance)
-    edges_in: HashMap<u32, Vec<(u32, u32)>>,
-    edges_undirected: HashMap<u32, Vec<(u32, u32)>>,
+    edges_out: AdjacencyList<u32>, // edge weights are distances
+    edges_in: AdjacencyList<u32>,
+    edges_undirected: AdjacencyList<u32>,
 }
 
 impl FromStr for Map {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid: Grid = s.parse()?;
+        let grid: TrailMap = s.parse()?;
         Ok(Self::from_grid(grid))
     }
 }
@@ -31,30 +29,24 @@ impl Map {
     pub fn print_graphviz(&self) {
         println!("digraph G {{");
 
-        for (idx_vx_from, outgoing_edges) in &self.edges_out {
-            for (idx_vx_to, dist) in outgoing_edges {
-                let vx_from = self.idx2vertex[idx_vx_from];
-                let vx_to = self.idx2vertex[idx_vx_to];
-                println!("\"{vx_from:?}\" -> \"{vx_to:?}\" [ label=\"{dist}\" ];")
-            }
+        for (idx_vx_from, idx_vx_to, dist) in self.edges_out.edges() {
+            let vx_from = self.idx2vertex[idx_vx_from];
+            let vx_to = self.idx2vertex[idx_vx_to];
+            println!("\"{vx_from:?}\" -> \"{vx_to:?}\" [ label=\"{dist}\" ];")
         }
 
         println!("}}");
     }
 
     pub fn longest_path_len_directed(&self) -> u32 {
-        // Assumption: graph is acyclic (a DAG)
-        let topsort = self.topological_sort();
+        let topsort = graph::topological_sort(&self.edges_out, [self.entrance_idx()])
+            .expect("slopes only allow walking downhill");
         let mut max_total_dist: HashMap<u32, u32> = HashMap::default();
         max_total_dist.insert(self.entrance_idx(), 0);
 
         for vx in topsort {
-            let Some(parents) = self.edges_in.get(&vx) else {
-                continue;
-            };
-
-            for (parent, parent_dist) in parents {
-                let total_dist_through_parent = parent_dist + max_total_dist[&parent];
+            for (parent, parent_dist) in self.edges_in.neighbours(&vx) {
+                let total_dist_through_parent = parent_dist + max_total_dist[parent];
                 max_total_dist
                     .entry(vx)
                     .and_modify(|d| *d = u32::max(*d, total_dist_through_parent))
@@ -86,10 +78,7 @@ impl Map {
                 continue;
             }
 
-            let Some(edges) = self.edges_undirected.get(&vx) else {
-                continue;
-            };
-            for (neighbour, dist) in edges {
+            for (neighbour, dist) in self.edges_undirected.neighbours(&vx) {
                 if visited.get(*neighbour) {
                     continue;
                 }
@@ -111,12 +100,12 @@ impl Map {
         self.vertex2idx[&self.grid.exit()]
     }
 
-    fn from_grid(grid: Grid) -> Self {
+    fn from_grid(grid: TrailMap) -> Self {
         let vertex2idx = grid.find_vertices();
         let edges_out = grid.find_edges_out(&vertex2idx);
         let idx2vertex = Self::invert_vertex2idx(&vertex2idx);
-        let edges_in = Self::make_edges_in_from_edges_out(&edges_out);
-        let edges_undirected = Self::make_undirected_edges_from_edges_out(&edges_out);
+        let edges_in = edges_out.reversed();
+        let edges_undirected = edges_out.to_undirected();
         Map {
             grid,
             vertex2idx,
@@ -130,101 +119,27 @@ impl Map {
     fn invert_vertex2idx(vertex2idx: &HashMap<Pos, u32>) -> HashMap<u32, Pos> {
         vertex2idx.iter().map(|(pos, idx)| (*idx, *pos)).collect()
     }
-
-    fn make_edges_in_from_edges_out(
-        edges_out: &HashMap<u32, Vec<(u32, u32)>>,
-    ) -> HashMap<u32, Vec<(u32, u32)>> {
-        let mut edges_in: HashMap<u32, Vec<(u32, u32)>> = HashMap::default();
-        for (&node_from, outgoing_edges) in edges_out {
-            for &(node_to, distance) in outgoing_edges {
-                edges_in
-                    .entry(node_to)
-                    .or_default()
-                    .push((node_from, distance));
-            }
-        }
-
-        edges_in
-    }
-
-    fn make_undirected_edges_from_edges_out(
-        edges_out: &HashMap<u32, Vec<(u32, u32)>>,
-    ) -> HashMap<u32, Vec<(u32, u32)>> {
-        let mut edges: HashMap<u32, Vec<(u32, u32)>> = HashMap::default();
-
-        for (&vx, outgoing_edges) in edges_out {
-            for &(neighbour, distance) in outgoing_edges {
-                edges.entry(vx).or_default().push((neighbour, distance));
-                edges.entry(neighbour).or_default().push((vx, distance));
-            }
-        }
-
-        edges
-    }
-
-    fn topological_sort(&self) -> Vec<u32> {
-        // using DFS. Assuming acyclic graph.
-        let mut topsort = VecDeque::new();
-        let mut visited = HashSet::default();
-        let mut stack = vec![(self.entrance_idx(), false)];
-
-        while let Some((pos, finished)) = stack.pop() {
-            if finished {
-                topsort.push_front(pos);
-                visited.insert(pos);
-                continue;
-            }
-
-            if visited.contains(&pos) {
-                continue;
-            }
-
-            stack.push((pos, true));
-
-            let Some(children) = self.edges_out.get(&pos) else {
-                continue;
-            };
-            for (neighbour, _dist) in children {
-                if !visited.contains(neighbour) {
-                    stack.push((*neighbour, false));
-                }
-            }
-        }
-
-        topsort.into()
-    }
 }
 
-struct Grid(Array2<u8>);
+struct TrailMap(Grid<u8>);
 
-impl FromStr for Grid {
+impl FromStr for TrailMap {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let linear_grid = s
-            .bytes()
-            .filter(|&b| b != b'\n')
-            .map(|b| match b {
-                b'#' | b'.' | b'^' | b'>' | b'v' | b'<' => Ok(b),
-                _ => Err("Invalid character"),
-            })
-            .collect::<Result<Vec<u8>, _>>()?;
-
-        let width = s.find('\n').unwrap_or(s.len());
-        let height = linear_grid.len() / width;
-        let grid = Array::from_vec(linear_grid).into_shape((height, width))?;
-
-        Ok(Grid(grid))
+        let grid = Grid::parse_with(s, |c| "#.^>v<".contains(c).then_some(c as u8))?;
+
+        Ok(TrailMap(grid))
     }
 }
 
-impl Grid {
+impl TrailMap {
     fn height(&self) -> usize {
-        self.0.shape()[0]
+        self.0.height()
     }
 
     fn width(&self) -> usize {
-        self.0.shape()[1]
+        self.0.width()
     }
 
     fn entrance(&self) -> Pos {
@@ -235,26 +150,19 @@ impl Grid {
         (self.height() - 1, self.width() - 2)
     }
 
-    fn neighbours(&self, pos: &Pos) -> [Option<Pos>; 4] {
-        let (y, x) = pos;
-
-        [(0, 1), (1, 0), (0, -1), (-1, 0)].map(|(dy, dx)| {
-            let neighbour_pos = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
-            if neighbour_pos.0 >= self.height() || neighbour_pos.1 >= self.width() {
-                return None;
-            }
-
-            if [b'.', b'^', b'>', b'v', b'<'].contains(&self.0[neighbour_pos]) {
-                Some(neighbour_pos)
-            } else {
-                None
-            }
+    /// The directions in which a position has a neighbour that is not a
+    /// forest tile.
+    fn open_directions(&self, pos: Pos) -> impl Iterator<Item = (isize, isize)> + '_ {
+        DIRECTIONS_4.into_iter().filter(move |&direction| {
+            self.0
+                .offset(pos, direction)
+                .is_some_and(|neighbour_pos| self.0[neighbour_pos] != b'#')
         })
     }
 
     fn find_vertices(&self) -> HashMap<Pos, u32> {
-        let height = self.0.shape()[0];
-        let width = self.0.shape()[1];
+        let height = self.height();
+        let width = self.width();
 
         let mut vertices = HashMap::default();
         vertices.insert(self.entrance(), 0);
@@ -267,7 +175,7 @@ impl Grid {
                     continue;
                 }
 
-                let n_neighbours = self.neighbours(&(y, x)).iter().flatten().count();
+                let n_neighbours = self.open_directions((y, x)).count();
                 if n_neighbours > 2 {
                     vertices.insert((y, x), idx);
                     idx += 1;
@@ -278,20 +186,15 @@ impl Grid {
         vertices
     }
 
-    fn find_edges_out(&self, vertices: &HashMap<Pos, u32>) -> HashMap<u32, Vec<(u32, u32)>> {
-        let mut edges: HashMap<u32, Vec<(u32, u32)>> = HashMap::default();
+    fn find_edges_out(&self, vertices: &HashMap<Pos, u32>) -> AdjacencyList<u32> {
+        let mut edges = AdjacencyList::new();
 
         for (vx, idx) in vertices {
-            for neighbour_pos in self.neighbours(vx).into_iter().flatten() {
-                let direction = (
-                    isize::try_from(neighbour_pos.0).unwrap() - isize::try_from(vx.0).unwrap(),
-                    isize::try_from(neighbour_pos.1).unwrap() - isize::try_from(vx.1).unwrap(),
-                );
-
+            for direction in self.open_directions(*vx) {
                 if let Some((target_vx, steps)) = self.walk_to_next_vertex(vx, direction, vertices)
                 {
                     let target_idx = vertices[&target_vx];
-                    edges.entry(*idx).or_default().push((target_idx, steps));
+                    edges.add_edge(*idx, target_idx, steps);
                 }
             }
         }
@@ -310,10 +213,7 @@ impl Grid {
         let mut n_steps = 0;
 
         loop {
-            let next_pos = (
-                pos.0.checked_add_signed(direction.0).unwrap(),
-                pos.1.checked_add_signed(direction.1).unwrap(),
-            );
+            let next_pos = self.0.offset(pos, direction).unwrap();
             let mut do_move = true;
 
             match self.0[next_pos] {
@@ -341,23 +241,15 @@ impl Grid {
                 b'#' => {
                     // we will hit a wall, so change direction.
                     let mut found = false;
-                    let excluded_neighbour = (
-                        // don't step further back to where we came from
-                        pos.0.checked_add_signed(-direction.0).unwrap(),
-                        pos.1.checked_add_signed(-direction.1).unwrap(),
-                    );
-
-                    for neighbour_pos in self.neighbours(&pos).into_iter().flatten() {
-                        if neighbour_pos == excluded_neighbour {
+                    // don't step further back to where we came from
+                    let excluded_direction = (-direction.0, -direction.1);
+
+                    for new_direction in self.open_directions(pos) {
+                        if new_direction == excluded_direction {
                             continue;
                         }
 
-                        direction = (
-                            isize::try_from(neighbour_pos.0).unwrap()
-                                - isize::try_from(pos.0).unwrap(),
-                            isize::try_from(neighbour_pos.1).unwrap()
-                                - isize::try_from(pos.1).unwrap(),
-                        );
+                        direction = new_direction;
                         found = true;
                         do_move = false;
                         break;
@@ -372,10 +264,7 @@ impl Grid {
             }
 
             if do_move {
-                pos = (
-                    pos.0.checked_add_signed(direction.0).unwrap(),
-                    pos.1.checked_add_signed(direction.1).unwrap(),
-                );
+                pos = next_pos;
                 n_steps += 1;
 
                 if vertices.contains_key(&pos) {
@@ -1,4 +1,6 @@
-use std::{error::Error, iter::zip, ops::Range, str::FromStr};
+use std::{error::Error, iter::zip, ops::RangeInclusive, str::FromStr};
+
+use crate::puzzles::geom::{Point3, Vec3};
 
 pub struct Hail(Vec<HailStone>);
 
@@ -16,7 +18,11 @@ impl FromStr for Hail {
 }
 
 impl Hail {
-    pub fn count_intersections_within_xy(&self, x_range: &Range<f64>, y_range: &Range<f64>) -> u32 {
+    pub fn count_intersections_within_xy(
+        &self,
+        x_range: &RangeInclusive<i64>,
+        y_range: &RangeInclusive<i64>,
+    ) -> u32 {
         let mut n = 0;
 
         for i in 0..self.0.len() {
@@ -24,16 +30,11 @@ impl Hail {
                 let stone_i = &self.0[i];
                 let stone_j = &self.0[j];
 
-                if let Some((x, y, t1, t2)) = stone_i.xyt_intersection(stone_j) {
-                    if x >= x_range.start
-                        && x <= x_range.end
-                        && y >= y_range.start
-                        && y <= y_range.end
-                        && t1 >= 0.0
-                        && t2 >= 0.0
-                    {
-                        n += 1;
-                    }
+                let Some(crossing) = stone_i.xy_crossing(stone_j) else {
+                    continue;
+                };
+                if crossing.is_in_future() && crossing.is_within(x_range, y_range) {
+                    n += 1;
                 }
             }
         }
@@ -41,7 +42,7 @@ impl Hail {
         n
     }
 
-    pub fn find_perfect_throw_velocity_and_position(&self) -> (i64, i64, i64, i64, i64, i64) {
+    pub fn find_perfect_throw_velocity_and_position(&self) -> (Point3<i64>, Vec3<i64>) {
         let n = self.0.len();
         let n = n.min(4); // just using the first 4 stones should be enough
 
@@ -54,8 +55,8 @@ impl Hail {
             let (vx, vy) = spiral.next();
 
             let mut intersections = zip(0..n, 1..n).map(|(i, j)| {
-                let stone_i = &self.0[i].add_velocity(&(-vx, -vy, 0));
-                let stone_j = &self.0[j].add_velocity(&(-vx, -vy, 0));
+                let stone_i = &self.0[i].add_velocity(Vec3::new(-vx, -vy, 0));
+                let stone_j = &self.0[j].add_velocity(Vec3::new(-vx, -vy, 0));
                 stone_i.intersection_at_integer_xy(stone_j)
             });
 
@@ -72,40 +73,35 @@ impl Hail {
 
         // find vz that makes all stones intersect in z as well
         let mut i = 0;
-        let vthrow_z = loop {
+        let vthrow = loop {
             i += 1;
             let vz = (i / 2) * ((i % 2) * -2 + 1); // 0, 1, -1, 2, -2, 3, ...
 
             let mut z_intersections = (0..n).map(|i| {
-                let stone = &self.0[i].add_velocity(&(-vthrow_x, -vthrow_y, -vz));
-                let t = if stone.vx != 0 {
-                    (x_throw - stone.x) / stone.vx
+                let stone = &self.0[i].add_velocity(Vec3::new(-vthrow_x, -vthrow_y, -vz));
+                let t = if stone.vel.x != 0 {
+                    (x_throw - stone.pos.x) / stone.vel.x
                 } else {
                     0
                 };
-                t * stone.vz + stone.z
+                t * stone.vel.z + stone.pos.z
             });
 
             let first = z_intersections.next().unwrap();
             if z_intersections.all(|z| z == first) {
-                break vz;
+                break Vec3::new(vthrow_x, vthrow_y, vz);
             }
         };
 
         // coordinate of intersection
-        let stone0 = &self.0[0].add_velocity(&(-vthrow_x, -vthrow_y, -vthrow_z));
-        let t0 = if stone0.vx != 0 {
-            (x_throw - stone0.x) / stone0.vx
+        let stone0 = &self.0[0].add_velocity(-vthrow);
+        let t0 = if stone0.vel.x != 0 {
+            (x_throw - stone0.pos.x) / stone0.vel.x
         } else {
             0
         };
-        let (x_throw, y_throw, z_throw) = (
-            stone0.x + t0 * stone0.vx,
-            stone0.y + t0 * stone0.vy,
-            stone0.z + t0 * stone0.vz,
-        );
 
-        (x_throw, y_throw, z_throw, vthrow_x, vthrow_y, vthrow_z)
+        (stone0.pos + stone0.vel * t0, vthrow)
     }
 }
 
@@ -152,12 +148,8 @@ impl SpiralCoords {
 }
 
 struct HailStone {
-    x: i64,
-    y: i64,
-    z: i64,
-    vx: i64,
-    vy: i64,
-    vz: i64,
+    pos: Point3<i64>,
+    vel: Vec3<i64>,
 }
 
 impl FromStr for HailStone {
@@ -165,63 +157,30 @@ impl FromStr for HailStone {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (pos_str, vel_str) = s.split_once(" @ ").ok_or("Invalid syntax")?;
-        let mut pos_iter = pos_str.split(", ");
-        let mut vel_iter = vel_str.split(", ");
-
-        let x = pos_iter.next().ok_or("Missing position")?.trim().parse()?;
-        let y = pos_iter.next().ok_or("Missing position")?.trim().parse()?;
-        let z = pos_iter.next().ok_or("Missing position")?.trim().parse()?;
-
-        let vx = vel_iter.next().ok_or("Missing velocity")?.trim().parse()?;
-        let vy = vel_iter.next().ok_or("Missing velocity")?.trim().parse()?;
-        let vz = vel_iter.next().ok_or("Missing velocity")?.trim().parse()?;
+        let pos = pos_str.parse()?;
+        let vel: Vec3<i64> = vel_str.parse()?;
 
-        if pos_iter.next().is_some() {
-            return Err("Too many positions".into());
-        }
-        if vel_iter.next().is_some() {
-            return Err("Too many velocities".into());
-        }
-        if vx == 0 || vy == 0 || vz == 0 {
+        if vel.x == 0 || vel.y == 0 || vel.z == 0 {
             return Err("Velocity x,y,z components may not be 0".into());
         }
 
-        Ok(HailStone {
-            x,
-            y,
-            z,
-            vx,
-            vy,
-            vz,
-        })
+        Ok(HailStone { pos, vel })
     }
 }
-type XYTIntersection = (f64, f64, f64, f64); // (x, y, t0, t1)
-
 impl HailStone {
-    fn slope(&self) -> f64 {
-        self.vy as f64 / self.vx as f64
-    }
-
-    fn intercept(&self) -> f64 {
-        self.y as f64 - self.x as f64 * self.slope()
-    }
-
     fn speed_squared_xy(&self) -> i64 {
-        self.vx * self.vx + self.vy * self.vy
+        self.vel.x * self.vel.x + self.vel.y * self.vel.y
     }
 
     fn is_parallel_to_xy(&self, other: &HailStone) -> bool {
-        let dotprod = self.vx * other.vx + self.vy * other.vy;
+        let dotprod = self.vel.x * other.vel.x + self.vel.y * other.vel.y;
         dotprod * dotprod == self.speed_squared_xy() * other.speed_squared_xy()
     }
 
-    fn add_velocity(&self, velocity: &(i64, i64, i64)) -> Self {
+    fn add_velocity(&self, velocity: Vec3<i64>) -> Self {
         HailStone {
-            vx: self.vx + velocity.0,
-            vy: self.vy + velocity.1,
-            vz: self.vz + velocity.2,
-            ..*self
+            pos: self.pos,
+            vel: self.vel + velocity,
         }
     }
 
@@ -233,21 +192,21 @@ impl HailStone {
             return None;
         }
 
-        let dx = other.x - self.x;
-        let dy = other.y - self.y;
+        let dx = other.pos.x - self.pos.x;
+        let dy = other.pos.y - self.pos.y;
 
         // Now it should hold that for integer n and m
-        // dx == n * self.vx - m * other.vx
-        // dy == m * self.vy - m * other.vy
+        // dx == n * self.vel.x - m * other.vel.x
+        // dy == m * self.vel.y - m * other.vel.y
         // from which it follows that
 
         let (nom_n, denom_n); // n == nom_n / denom_n
-        if other.vx == 0 {
+        if other.vel.x == 0 {
             nom_n = dx;
-            denom_n = self.vx;
+            denom_n = self.vel.x;
         } else {
-            nom_n = dx * other.vy - dy * other.vx;
-            denom_n = self.vx * other.vy - other.vx * self.vy;
+            nom_n = dx * other.vel.y - dy * other.vel.x;
+            denom_n = self.vel.x * other.vel.y - other.vel.x * self.vel.y;
         }
         if nom_n % denom_n != 0 {
             return None;
@@ -255,57 +214,93 @@ impl HailStone {
         let n = nom_n / denom_n;
 
         let (nom_m, denom_m); // m == nom_m / denom_m
-        if self.vx == 0 {
+        if self.vel.x == 0 {
             nom_m = -dx;
-            denom_m = other.vx;
+            denom_m = other.vel.x;
         } else {
-            nom_m = dy * self.vx - dx * self.vy;
-            denom_m = other.vx * self.vy - self.vx * other.vy;
+            nom_m = dy * self.vel.x - dx * self.vel.y;
+            denom_m = other.vel.x * self.vel.y - self.vel.x * other.vel.y;
         }
         if nom_m % denom_m != 0 {
             return None;
         }
         let _m = nom_m / denom_m;
 
-        let x = self.x + n * self.vx; // == other.x + m * other.vx
-        let y = self.y + n * self.vy; // == other.y + m * other.vy
+        let x = self.pos.x + n * self.vel.x; // == other.pos.x + m * other.vel.x
+        let y = self.pos.y + n * self.vel.y; // == other.pos.y + m * other.vel.y
         Some((x, y))
     }
 
-    fn xyt_intersection(&self, other: &HailStone) -> Option<XYTIntersection> {
-        // No intersection if one of the stones is not moving or they are
-        // parallel, and not moving is impossible as validated when parsing the
-        // input.
-        if self.is_parallel_to_xy(other) {
+    /// Where the paths of self and other cross in the xy plane. None if they
+    /// move in parallel.
+    fn xy_crossing(&self, other: &HailStone) -> Option<XyCrossing> {
+        // Solve self.pos + t0 * self.vel == other.pos + t1 * other.vel for x
+        // and y with Cramer's rule.
+        let (x0, y0) = (i128::from(self.pos.x), i128::from(self.pos.y));
+        let (vx0, vy0) = (i128::from(self.vel.x), i128::from(self.vel.y));
+        let (vx1, vy1) = (i128::from(other.vel.x), i128::from(other.vel.y));
+        let dx = i128::from(other.pos.x) - x0;
+        let dy = i128::from(other.pos.y) - y0;
+
+        let det = vx0 * -vy1 + vx1 * vy0;
+        if det == 0 {
             return None;
         }
 
-        let x_intersect = (other.intercept() - self.intercept()) / (self.slope() - other.slope());
-        let y_intersect = self.intercept() + x_intersect * self.slope();
+        let t0 = dx * -vy1 + vx1 * dy;
+        let t1 = vx0 * dy - vy0 * dx;
+        Some(XyCrossing {
+            times: [t0, t1],
+            xy: [x0 * det + t0 * vx0, y0 * det + t0 * vy0],
+            det,
+        })
+    }
+}
 
-        let t_intersect_self = (y_intersect - self.y as f64) / self.vy as f64;
-        let t_intersect_other = (y_intersect - other.y as f64) / other.vy as f64;
+/// The point where the paths of two stones cross in the xy plane, as
+/// numerators over a common denominator. Comparing the numerators to bounds
+/// times the denominator is exact, without reducing any fractions.
+struct XyCrossing {
+    /// The times at which the stones reach the crossing, times `det`.
+    times: [i128; 2],
+    /// The position of the crossing, times `det`.
+    xy: [i128; 2],
+    det: i128,
+}
+
+impl XyCrossing {
+    /// Whether both stones reach the crossing now or later.
+    fn is_in_future(&self) -> bool {
+        self.times
+            .iter()
+            .all(|t| t.signum() * self.det.signum() >= 0)
+    }
 
-        Some((
-            x_intersect,
-            y_intersect,
-            t_intersect_self,
-            t_intersect_other,
-        ))
+    fn is_within(&self, x_range: &RangeInclusive<i64>, y_range: &RangeInclusive<i64>) -> bool {
+        let contains = |range: &RangeInclusive<i64>, numer: i128| {
+            let (start, end) = (i128::from(*range.start()), i128::from(*range.end()));
+            if self.det > 0 {
+                (start * self.det..=end * self.det).contains(&numer)
+            } else {
+                (end * self.det..=start * self.det).contains(&numer)
+            }
+        };
+        contains(x_range, self.xy[0]) && contains(y_range, self.xy[1])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Day;
+    use crate::{day, year, PuzzleId};
 
     use super::*;
 
     #[test]
     fn test_count_intersections_within_xy() {
-        let input = crate::template::read_file("examples", Day::new(24).unwrap());
+        let input =
+            crate::template::read_file("examples", PuzzleId::new(year!(2023), day!(24))).unwrap();
         let hail: Hail = input.parse().unwrap();
-        let range = 7.0f64..27f64;
+        let range = 7..=27;
         let count = hail.count_intersections_within_xy(&range, &range);
 
         assert_eq!(count, 2);
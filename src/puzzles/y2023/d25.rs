@@ -0,0 +1,138 @@
+use std::{error::Error, str::FromStr};
+
+use rand::prelude::*;
+use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::puzzles::graph::UnionFind;
+
+pub struct Wiring {
+    idxs: HashMap<usize, String>,
+    connections: Vec<(usize, usize)>,
+}
+
+impl FromStr for Wiring {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = HashSet::<String>::default();
+        let mut str_cnxns = HashMap::<String, Vec<String>>::default();
+
+        for line in s.lines() {
+            let (from_node, to) = line.split_once(": ").ok_or("Missing separator")?;
+            let to_nodes: Vec<String> = to.split_whitespace().map(String::from).collect();
+            components.insert(from_node.into());
+            components.extend(to_nodes.iter().cloned());
+            str_cnxns.insert(from_node.into(), to_nodes);
+        }
+
+        let str2idx: HashMap<String, usize> = components
+            .iter()
+            .enumerate()
+            .map(|(idx, str)| (str.to_string(), idx))
+            .collect();
+
+        let mut idx_cnxns = Vec::new();
+        for component in components {
+            if !str_cnxns.contains_key(&component) {
+                continue;
+            }
+
+            let idx_from = str2idx[&component];
+            let idxs_to: Vec<usize> = str_cnxns[&component].iter().map(|s| str2idx[s]).collect();
+            for idx_to in idxs_to {
+                idx_cnxns.push((idx_from, idx_to));
+            }
+        }
+
+        let idx2string: HashMap<usize, String> = str2idx.into_iter().map(|(s, i)| (i, s)).collect();
+
+        Ok(Wiring {
+            idxs: idx2string,
+            connections: idx_cnxns,
+        })
+    }
+}
+
+impl Wiring {
+    pub fn min_cut(&self) -> MinCut {
+        // Repeated Karger's algorithm and return best (best being the cut with
+        // the minimum number of edges; there could in general be multiple
+        // different such minimum cuts, though in the puzzle input probably
+        // not).
+
+        // for c * n_choose_2 * ln(n) trials, the success probability is at
+        // least 1 - (1 / n ^ c), where n is the number of nodes. However, we
+        // can just search until we have a minimum cut of 3 since it is already
+        // given that this is the minimum.
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut best_cut = self.kargers_algorithm(&mut rng);
+        while best_cut.edges.len() > 3 {
+            let min_cut = self.kargers_algorithm(&mut rng);
+
+            let n_edge = min_cut.edges.len();
+            let n_edge_best = best_cut.edges.len();
+            if n_edge < n_edge_best {
+                best_cut = min_cut;
+            }
+        }
+
+        best_cut
+    }
+
+    fn kargers_algorithm(&self, rng: &mut impl Rng) -> MinCut {
+        assert!(
+            self.idxs.len() > 1,
+            "Can't cut a graph of less than 2 nodes"
+        );
+
+        let mut edges = self.connections.clone();
+        edges.shuffle(rng);
+        let edges = edges;
+
+        let mut super_nodes: UnionFind<usize> = self.idxs.keys().cloned().collect();
+        let mut n = 0;
+        while super_nodes.n_sets() > 2 {
+            let (from, to) = &edges[n];
+            super_nodes.union(from, to);
+            n += 1;
+        }
+
+        let remaining_edges = edges[n..]
+            .iter()
+            .filter(|(node_a, node_b)| {
+                let root_a = super_nodes.find_root(node_a).unwrap();
+                let root_b = super_nodes.find_root(node_b).unwrap();
+                root_a != root_b
+            }) // ignore edges within the same component
+            .cloned()
+            .collect();
+
+        MinCut {
+            edges: remaining_edges,
+            components: super_nodes,
+        }
+    }
+}
+
+pub struct MinCut {
+    edges: Vec<(usize, usize)>, // The edges between the 2 components, e.g. across the cut
+    components: UnionFind<usize>,
+}
+
+impl MinCut {
+    pub fn size(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn component_sizes(&self) -> (usize, usize) {
+        let (node_a, node_b) = &self.edges[0];
+        let root_a = self.components.find_root(node_a).unwrap();
+        let root_b = self.components.find_root(node_b).unwrap();
+
+        (
+            self.components.size_of_set(root_a).unwrap(),
+            self.components.size_of_set(root_b).unwrap(),
+        )
+    }
+}
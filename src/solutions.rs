@@ -0,0 +1,6 @@
+// Every solution binary in `src/bin`, compiled into the library as a module
+// `yYYYY::dayNN`, plus the `SOLUTIONS` registry of the `DaySolution` that the
+// `solution!` macro defines for each puzzle. Both are generated by `build.rs`,
+// and only with the `in_process` feature, so that a solution that does not
+// compile does not break the library.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
@@ -0,0 +1,118 @@
+/// Module that reads the accepted answers of a day, so that solutions can be
+/// checked for regressions.
+///
+/// Answers live in `data/YYYY/answers/NN.txt`. The first line holds the answer to
+/// part 1, the second line the answer to part 2. An empty or missing line means
+/// that no answer has been recorded for that part yet.
+use std::{env, fmt::Display, fs};
+
+use crate::PuzzleId;
+
+use super::{config, ANSI_ITALIC, ANSI_RESET};
+
+/// The outcome of comparing a result to the recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unrecorded,
+}
+
+impl Verdict {
+    /// Whether the verdict should be reported as a regression.
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "✔"),
+            Verdict::Wrong { expected } => write!(f, "✘ expected {expected}"),
+            Verdict::Unrecorded => write!(f, "{ANSI_ITALIC}no recorded answer{ANSI_RESET}"),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_answers(puzzle: PuzzleId) -> String {
+    format!(
+        "{}/answers/{}.txt",
+        config::get().year_dir(puzzle.year),
+        puzzle.day
+    )
+}
+
+/// Read the recorded answer for one part of a puzzle, if there is one.
+#[must_use]
+pub fn read(puzzle: PuzzleId, part: u8) -> Option<String> {
+    let path = env::current_dir().ok()?.join(get_path_for_answers(puzzle));
+    let contents = fs::read_to_string(path).ok()?;
+    parse_answer(&contents, part)
+}
+
+fn parse_answer(contents: &str, part: u8) -> Option<String> {
+    let line = contents.lines().nth(usize::from(part).checked_sub(1)?)?;
+    let answer = line.trim();
+
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_string())
+    }
+}
+
+/// Compare a result to the answer recorded for a puzzle and part. A missing
+/// result counts as wrong if an answer has been recorded.
+#[must_use]
+pub fn check(puzzle: PuzzleId, part: u8, result: Option<&str>) -> Verdict {
+    compare(read(puzzle, part), result)
+}
+
+fn compare(expected: Option<String>, result: Option<&str>) -> Verdict {
+    match expected {
+        None => Verdict::Unrecorded,
+        Some(expected) if result.map(str::trim) == Some(expected.as_str()) => Verdict::Correct,
+        Some(expected) => Verdict::Wrong { expected },
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, parse_answer, Verdict};
+
+    #[test]
+    fn parses_both_parts() {
+        let contents = "142\n281\n";
+        assert_eq!(parse_answer(contents, 1), Some("142".into()));
+        assert_eq!(parse_answer(contents, 2), Some("281".into()));
+        assert_eq!(parse_answer(contents, 3), None);
+    }
+
+    #[test]
+    fn parses_unrecorded_part() {
+        let contents = "\n281\n";
+        assert_eq!(parse_answer(contents, 1), None);
+        assert_eq!(parse_answer(contents, 2), Some("281".into()));
+        assert_eq!(parse_answer("142", 2), None);
+    }
+
+    #[test]
+    fn compares_results() {
+        assert_eq!(compare(Some("42".into()), Some("42")), Verdict::Correct);
+        assert_eq!(compare(None, Some("42")), Verdict::Unrecorded);
+        assert_eq!(
+            compare(Some("42".into()), Some("43")),
+            Verdict::Wrong {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            compare(Some("42".into()), None),
+            Verdict::Wrong {
+                expected: "42".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,480 @@
+/// A built-in client for the Advent of Code website, which downloads inputs and
+/// puzzle descriptions and submits answers.
+///
+/// The session token is read from the `AOC_SESSION` environment variable or,
+/// like `aoc-cli` does, from `~/.adventofcode.session`. Set `AOC_SESSION_FILE`
+/// to read it from another file. The server and backend are configured with
+/// `submit.base_url` and `submit.backend`, and `submit.contact` is sent along
+/// with every request, see [`super::config`].
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{
+    aoc_cli,
+    config::{self, SubmitBackend},
+    html,
+};
+use crate::PuzzleId;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this client to the Advent of Code servers, as requested by their
+/// maintainer: the repository of the project, or its name if Cargo.toml has no
+/// `repository`, and who to contact about it.
+fn user_agent(contact: Option<&str>) -> String {
+    let project = match env!("CARGO_PKG_REPOSITORY") {
+        "" => env!("CARGO_PKG_NAME"),
+        repository => repository,
+    };
+    match contact {
+        Some(contact) => format!("{project} by {contact}"),
+        None => project.into(),
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    /// The server responded with an error status.
+    Status(u16),
+    Transport(String),
+    IO(io::Error),
+    Cli(aoc_cli::AocCommandError),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<aoc_cli::AocCommandError> for Error {
+    fn from(e: aoc_cli::AocCommandError) -> Self {
+        Error::Cli(e)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => Error::Status(status),
+            ureq::Error::Transport(e) => Error::Transport(e.to_string()),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session token found. Set AOC_SESSION or create ~/.adventofcode.session."
+            ),
+            Error::Status(400) => write!(
+                f,
+                "the server rejected the request (status 400), is the session token valid?"
+            ),
+            Error::Status(404) => write!(
+                f,
+                "the server could not find the puzzle (status 404), has it been unlocked yet?"
+            ),
+            Error::Status(status) => write!(f, "the server responded with status {status}."),
+            Error::Transport(e) => write!(f, "could not reach the server: {e}"),
+            Error::IO(e) => write!(f, "could not write output files to file system: {e}"),
+            Error::Cli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The response of the website to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Correct(String),
+    Incorrect(String),
+    /// The previous answer was submitted too recently; the message says how long to wait.
+    TooSoon(String),
+    /// The part was already solved, or the first part has to be solved first.
+    WrongLevel(String),
+    Unknown(String),
+}
+
+impl Submission {
+    fn from_html(page: &str) -> Self {
+        let message = html::articles(page)
+            .first()
+            .map_or_else(|| html::to_text(page), |article| html::to_text(article));
+
+        Self::from_text(message)
+    }
+
+    /// Classify the response of the website by its message, e.g. the output of
+    /// `aoc-cli`. Line breaks are joined, as `aoc-cli` wraps long lines.
+    #[must_use]
+    pub fn from_text(message: impl AsRef<str>) -> Self {
+        let message = message
+            .as_ref()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if message.contains("That's the right answer") {
+            Submission::Correct(message)
+        } else if message.contains("That's not the right answer") {
+            Submission::Incorrect(message)
+        } else if message.contains("You gave an answer too recently") {
+            Submission::TooSoon(message)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Submission::WrongLevel(message)
+        } else {
+            Submission::Unknown(message)
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Submission::Correct(message)
+            | Submission::Incorrect(message)
+            | Submission::TooSoon(message)
+            | Submission::WrongLevel(message)
+            | Submission::Unknown(message) => message,
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Submission::Correct(_) => "⭐️",
+            Submission::Incorrect(_) => "✘",
+            _ => "🕒",
+        };
+        write!(f, "{symbol} {}", self.message())
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, contact: Option<&str>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent(contact))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// Create a client from the session token in the environment and the
+    /// configured base URL and contact.
+    pub fn from_env() -> Result<Self, Error> {
+        let session = read_session().ok_or(Error::MissingSession)?;
+        let submit = &config::get().submit;
+        Ok(Client::new(
+            &submit.base_url,
+            &session,
+            submit.contact.as_deref(),
+        ))
+    }
+
+    /// The personal input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        self.get(&self.url(puzzle, "/input"))
+    }
+
+    /// The description of a puzzle as markdown, including the second part
+    /// once the first part is solved.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let page = self.get(&self.url(puzzle, ""))?;
+        Ok(html::to_markdown(&page))
+    }
+
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<Submission, Error> {
+        let page = self
+            .agent
+            .post(&self.url(puzzle, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Ok(Submission::from_html(&page))
+    }
+
+    fn get(&self, url: &str) -> Result<String, Error> {
+        let body = self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+
+        Ok(body)
+    }
+
+    fn url(&self, puzzle: PuzzleId, path: &str) -> String {
+        format!(
+            "{}/{}/day/{}{path}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            PathBuf::from(home).join(".adventofcode.session")
+        }
+    };
+
+    let session = fs::read_to_string(path).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Talks to the website either through the built-in [`Client`] or through
+/// `aoc-cli`, which is used when no session token is configured or when the
+/// `submit.backend` setting is `aoc-cli`.
+pub enum Backend {
+    Native(Client),
+    Cli,
+}
+
+impl Backend {
+    pub fn from_env() -> Result<Self, Error> {
+        match config::get().submit.backend {
+            SubmitBackend::Cli => {
+                aoc_cli::check()?;
+                Ok(Backend::Cli)
+            }
+            SubmitBackend::Native => Client::from_env().map(Backend::Native),
+            SubmitBackend::Auto => match Client::from_env() {
+                Ok(client) => Ok(Backend::Native(client)),
+                Err(Error::MissingSession) if aoc_cli::check().is_ok() => Ok(Backend::Cli),
+                Err(e) => Err(e),
+            },
+        }
+    }
+
+    /// Download the input and description of a puzzle to the `data` folder.
+    pub fn download(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let input_path = aoc_cli::get_input_path(puzzle);
+        let puzzle_path = aoc_cli::get_puzzle_path(puzzle);
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+
+        match self {
+            Backend::Native(client) => {
+                fs::write(&input_path, client.input(puzzle)?)?;
+                fs::write(&puzzle_path, client.puzzle(puzzle)?)?;
+
+                println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+                println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+            }
+            Backend::Cli => {
+                aoc_cli::download(puzzle)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the description of a puzzle, saving it to the `data` folder.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let puzzle_path = aoc_cli::get_puzzle_path(puzzle);
+        create_parent_dir(&puzzle_path)?;
+
+        match self {
+            Backend::Native(client) => {
+                let description = client.puzzle(puzzle)?;
+                fs::write(&puzzle_path, &description)?;
+                print!("{description}");
+            }
+            Backend::Cli => {
+                aoc_cli::read(puzzle)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Submit an answer, returning the response of the website.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<Submission, Error> {
+        match self {
+            Backend::Native(client) => client.submit(puzzle, part, answer),
+            Backend::Cli => {
+                let output = aoc_cli::submit(puzzle, part, answer)?;
+                Ok(Submission::from_text(String::from_utf8_lossy(
+                    &output.stdout,
+                )))
+            }
+        }
+    }
+}
+
+/// The data folders of a year only exist once one of its puzzles was scaffolded
+/// or downloaded.
+fn create_parent_dir(path: &str) -> Result<(), io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{Client, Error, Submission};
+    use crate::{day, year, PuzzleId};
+
+    /// Serve a single request with a canned response, and send the request back.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            sender.send(request).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, request) = mock_server(200, "1abc2\n");
+        let client = Client::new(&url, "secret", Some("me@example.com"));
+
+        assert_eq!(
+            client.input(PuzzleId::new(year!(2023), day!(1))).unwrap(),
+            "1abc2\n"
+        );
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+        assert!(request.contains("advent_of_code by me@example.com"));
+    }
+
+    #[test]
+    fn downloads_puzzle_as_markdown() {
+        let (url, _) = mock_server(
+            200,
+            "<main><article><h2>--- Day 5 ---</h2></article></main>",
+        );
+        let client = Client::new(&url, "secret", None);
+
+        assert_eq!(
+            client.puzzle(PuzzleId::new(year!(2023), day!(5))).unwrap(),
+            "## --- Day 5 ---\n"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, request) = mock_server(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = Client::new(&url, "secret", None);
+
+        let submission = client
+            .submit(PuzzleId::new(year!(2023), day!(1)), 2, "281")
+            .unwrap();
+        assert!(matches!(submission, Submission::Correct(_)));
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=281"));
+    }
+
+    #[test]
+    fn reports_error_status() {
+        let (url, _) = mock_server(400, "Puzzle inputs differ by user.");
+        let client = Client::new(&url, "expired", None);
+
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2023), day!(1))),
+            Err(Error::Status(400))
+        ));
+    }
+
+    #[test]
+    fn parses_submission_responses() {
+        let wrong = Submission::from_html(
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        );
+        assert_eq!(
+            wrong,
+            Submission::Incorrect("That's not the right answer; your answer is too high.".into())
+        );
+
+        let too_soon = Submission::from_html(
+            "<article><p>You gave an answer too recently. You have 42s left to wait.</p></article>",
+        );
+        assert!(matches!(too_soon, Submission::TooSoon(_)));
+
+        let wrapped = Submission::from_text(
+            "That's not the right\nanswer; your answer is too low.  Please wait one minute\nbefore trying again.\n",
+        );
+        assert_eq!(
+            wrapped,
+            Submission::Incorrect(
+                "That's not the right answer; your answer is too low. Please wait one minute before trying again.".into()
+            )
+        );
+    }
+}
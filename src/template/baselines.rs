@@ -0,0 +1,352 @@
+/// Module that stores benchmark timings as named baselines, and compares later
+/// runs against them.
+///
+/// Baselines are kept per year. A baseline lives in `data/YYYY/baselines/<name>.jsonl`
+/// and holds one JSON object
+/// per benched part, e.g. `{"day":17,"part":2,"duration_ns":96500000,"spread_ns":1300000}`.
+use std::{collections::BTreeMap, fmt::Display, fs, io, time::Duration};
+
+use crate::template::{
+    config,
+    json::{self, Value},
+    readme_benchmarks::{PartTiming, Timings},
+    report::PARSE_PART,
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidName(String),
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidName(name) => write!(
+                f,
+                "invalid baseline name \"{name}\", use only letters, digits, '-' and '_'"
+            ),
+            Error::Parser(e) => write!(f, "could not parse baseline: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// The options of the `all` command that deal with baselines.
+#[derive(Debug, Clone, Default)]
+pub struct BaselineArgs {
+    /// Store the timings of this run under this name.
+    pub save: Option<String>,
+    /// Compare the timings of this run to the baseline with this name.
+    pub compare: Option<String>,
+    /// Slowdown in percent above which a part counts as a regression.
+    pub threshold: Option<f64>,
+}
+
+impl BaselineArgs {
+    pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+    pub fn is_empty(&self) -> bool {
+        self.save.is_none() && self.compare.is_none()
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold.unwrap_or(Self::DEFAULT_THRESHOLD)
+    }
+}
+
+/// The timings of all benched parts, by day and part.
+pub type Baseline = BTreeMap<(Day, u8), PartTiming>;
+
+fn get_path(year: Year, name: &str) -> Result<String, Error> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        Ok(format!(
+            "{}/baselines/{name}.jsonl",
+            config::get().year_dir(year)
+        ))
+    } else {
+        Err(Error::InvalidName(name.into()))
+    }
+}
+
+#[must_use]
+pub fn from_timings(timings: &[Timings]) -> Baseline {
+    timings
+        .iter()
+        .flat_map(|t| {
+            [
+                (t.day, PARSE_PART, t.parse),
+                (t.day, 1, t.part_1),
+                (t.day, 2, t.part_2),
+            ]
+        })
+        .filter_map(|(day, part, timing)| Some(((day, part), timing?)))
+        .collect()
+}
+
+fn to_string(baseline: &Baseline) -> String {
+    baseline
+        .iter()
+        .map(|((day, part), timing)| {
+            let spread = timing
+                .spread
+                .map_or(Value::Null, |s| Value::from(s.as_nanos()));
+            json::write_object(&[
+                ("day", Value::from(day.into_inner())),
+                ("part", Value::from(*part)),
+                ("duration_ns", Value::from(timing.duration.as_nanos())),
+                ("spread_ns", spread),
+            ])
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+fn from_str(s: &str) -> Result<Baseline, Error> {
+    let parse_line = |line: &str| -> Option<((Day, u8), PartTiming)> {
+        let object = json::parse_object(line).ok()?;
+        let day = Day::new(u8::try_from(object.get("day")?.as_u64()?).ok()?)?;
+        let part = u8::try_from(object.get("part")?.as_u64()?).ok()?;
+        let duration = Duration::from_nanos(object.get("duration_ns")?.as_u64()?);
+        let spread = match object.get("spread_ns") {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(Duration::from_nanos(value.as_u64()?)),
+        };
+        Some(((day, part), PartTiming { duration, spread }))
+    };
+
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_line(line).ok_or_else(|| Error::Parser(line.into())))
+        .collect()
+}
+
+pub fn save(year: Year, name: &str, baseline: &Baseline) -> Result<String, Error> {
+    let path = get_path(year, name)?;
+    fs::create_dir_all(format!("{}/baselines", config::get().year_dir(year)))?;
+    fs::write(&path, to_string(baseline))?;
+    Ok(path)
+}
+
+pub fn load(year: Year, name: &str) -> Result<Baseline, Error> {
+    from_str(&fs::read_to_string(get_path(year, name)?)?)
+}
+
+/// The change in timing of a part, relative to a baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+    /// The spreads of both timings added up, zero if they are unknown.
+    pub spread: Duration,
+}
+
+impl Delta {
+    /// The relative change in percent, positive if the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        let before = self.before.as_secs_f64();
+        if before == 0.0 {
+            return 0.0;
+        }
+        (self.after.as_secs_f64() - before) / before * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent, and by
+    /// more than the spread, so that noise on fast parts isn't flagged.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold && self.after > self.before + self.spread
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // use a proper minus sign, which lines up with the plus sign.
+        let change = format!("{:+.0}%", self.change_percent()).replace('-', "−");
+        let part = match self.part {
+            PARSE_PART => "parse".into(),
+            part => format!("part {part}"),
+        };
+        write!(
+            f,
+            "Day {} {}: {:.1?} → {:.1?}, {}",
+            self.day.into_inner(),
+            part,
+            self.before,
+            self.after,
+            change
+        )
+    }
+}
+
+/// Compare the parts that were benched in both the baseline and the current run.
+#[must_use]
+pub fn compare(baseline: &Baseline, current: &Baseline) -> Vec<Delta> {
+    current
+        .iter()
+        .filter_map(|(&(day, part), after)| {
+            let before = baseline.get(&(day, part))?;
+            Some(Delta {
+                day,
+                part,
+                before: before.duration,
+                after: after.duration,
+                spread: before.spread.unwrap_or_default() + after.spread.unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Print the deltas, flagging the ones above the threshold. Returns the number
+/// of regressions.
+pub fn print_comparison(name: &str, deltas: &[Delta], threshold: f64) -> usize {
+    println!("\n{ANSI_BOLD}Compared to baseline \"{name}\":{ANSI_RESET}");
+
+    if deltas.is_empty() {
+        println!("No parts in common.");
+    }
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        if delta.is_regression(threshold) {
+            regressions += 1;
+            println!("{delta} ✘ regression");
+        } else {
+            println!("{delta}");
+        }
+    }
+
+    regressions
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, from_str, from_timings, get_path, to_string, Delta};
+    use crate::template::readme_benchmarks::{PartTiming, Timings};
+    use crate::{day, year};
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                parse: None,
+                part_1: Some(PartTiming {
+                    duration: Duration::from_micros(10),
+                    spread: Some(Duration::from_micros(1)),
+                }),
+                part_2: None,
+                total_nanos: 1e+4,
+            },
+            Timings {
+                day: day!(17),
+                parse: Some(Duration::from_micros(1_500).into()),
+                part_1: Some(Duration::from_micros(22_200).into()),
+                part_2: Some(Duration::from_micros(96_500).into()),
+                total_nanos: 1.187e+8,
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trips_baselines() {
+        let baseline = from_timings(&get_mock_timings());
+        assert_eq!(baseline.len(), 4);
+
+        let s = to_string(&baseline);
+        assert_eq!(s.lines().count(), 4);
+        assert_eq!(
+            s.lines().next().unwrap(),
+            r#"{"day":1,"part":1,"duration_ns":10000,"spread_ns":1000}"#
+        );
+        assert_eq!(from_str(&s).unwrap(), baseline);
+        assert!(from_str("not a baseline").is_err());
+    }
+
+    #[test]
+    fn validates_names() {
+        assert_eq!(
+            get_path(year!(2023), "main-1_a").unwrap(),
+            "data/2023/baselines/main-1_a.jsonl"
+        );
+        assert!(get_path(year!(2023), "").is_err());
+        assert!(get_path(year!(2023), "../README").is_err());
+    }
+
+    #[test]
+    fn compares_common_parts() {
+        let before = from_timings(&get_mock_timings());
+        let mut after = before.clone();
+        after.remove(&(day!(1), 1));
+        after.insert((day!(17), 2), Duration::from_micros(71_000).into());
+        after.insert((day!(3), 1), Duration::from_micros(50).into());
+
+        let deltas = compare(&before, &after);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].to_string(), "Day 17 parse: 1.5ms → 1.5ms, +0%");
+        assert_eq!(deltas[1].change_percent(), 0.0);
+        assert_eq!(
+            deltas[2].to_string(),
+            "Day 17 part 2: 96.5ms → 71.0ms, −26%"
+        );
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let delta = Delta {
+            day: day!(5),
+            part: 1,
+            before: Duration::from_millis(100),
+            after: Duration::from_millis(115),
+            spread: Duration::from_millis(5),
+        };
+        assert_eq!(delta.to_string(), "Day 5 part 1: 100.0ms → 115.0ms, +15%");
+        assert!(delta.is_regression(10.0));
+        assert!(!delta.is_regression(20.0));
+    }
+
+    #[test]
+    fn ignores_slowdowns_within_the_spread() {
+        let before = PartTiming {
+            duration: Duration::from_nanos(200),
+            spread: Some(Duration::from_nanos(40)),
+        };
+        let after = PartTiming {
+            duration: Duration::from_nanos(260),
+            spread: Some(Duration::from_nanos(30)),
+        };
+        let deltas = compare(
+            &[((day!(6), 1), before)].into(),
+            &[((day!(6), 1), after)].into(),
+        );
+        assert_eq!(deltas[0].spread, Duration::from_nanos(70));
+        assert!(!deltas[0].is_regression(10.0));
+
+        let after = PartTiming {
+            duration: Duration::from_nanos(280),
+            ..after
+        };
+        let deltas = compare(
+            &[((day!(6), 1), before)].into(),
+            &[((day!(6), 1), after)].into(),
+        );
+        assert!(deltas[0].is_regression(10.0));
+    }
+}
@@ -1,39 +1,122 @@
-use std::io;
+use std::{env, fmt::Display, io, process, process::Command, thread};
 
 use crate::template::{
+    baselines::{self, BaselineArgs},
+    memory::format_bytes,
+    read_file,
     readme_benchmarks::{self, Timings},
+    report::{Failure, PartReport},
+    runner::RunSettings,
+    solution,
+    trace::Profile,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// Run all days of a year, either through their binaries or, with `in_process`, through the
+/// solution registry of this binary. Binaries of up to `jobs` days run concurrently;
+/// by default this is the number of CPUs, or 1 for timed runs so that benchmarks
+/// are not skewed.
+/// Timed runs can be saved as a named baseline, or compared to one; parts that
+/// got slower than the baseline threshold count as regressions.
+/// Days that panic or run out of their `--timeout` count as failures, but do not
+/// stop the other days.
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    settings: RunSettings,
+    in_process: bool,
+    jobs: Option<usize>,
+    baseline: BaselineArgs,
+) {
+    let (is_timed, is_checked) = (settings.time, settings.check);
+
+    if !is_timed && !baseline.is_empty() {
+        eprintln!("Baselines require timings. Try running with --time.");
+        process::exit(1);
+    }
+
+    if in_process && settings.timeout.is_some() {
+        eprintln!("--timeout is not supported with --in-process, as the days share this process.");
+        process::exit(1);
+    }
+
+    if in_process && (!cfg!(feature = "in_process") || settings.mem && !cfg!(feature = "mem")) {
+        rerun_in_process(settings);
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut regressions: Vec<Day> = vec![];
+    let mut mem_reports: Vec<PartReport> = vec![];
+    let mut profile = Profile::default();
+    let mut summary = Summary::default();
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    let jobs = jobs.unwrap_or_else(|| default_jobs(is_timed));
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    if jobs > 1 && is_timed {
+        eprintln!("Warning: running {jobs} days concurrently, timings might be skewed.");
+    }
+
+    let mut record = |day: Day, reports: Vec<PartReport>, success: bool| {
+        if is_checked && reports.iter().any(PartReport::is_regression) {
+            regressions.push(day);
+        }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        summary.add(DayOutcome::from_reports(&reports, success));
 
-        if output.is_empty() {
+        if reports.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            timings.push(Timings::from_reports(day, &reports));
+            for report in &reports {
+                if let Some(part_profile) = &report.profile {
+                    profile.merge(part_profile);
+                }
+            }
+            mem_reports.extend(reports.into_iter().filter(|r| r.mem.is_some()));
         }
-    });
+    };
+
+    if in_process {
+        if jobs > 1 {
+            eprintln!("Warning: --jobs is not supported with --in-process, running days serially.");
+        }
+
+        all_days().for_each(|day| {
+            print_header(day);
+            let reports = run_in_process(PuzzleId::new(year, day));
+            let success = !reports.iter().any(|r| r.is_regression() || r.is_failure());
+            record(day, reports, success);
+        });
+    } else {
+        child_commands::run_all(year, jobs, settings, is_release, |day, output| {
+            print_header(day);
+            output.print();
+            record(day, output.reports(), output.success);
+        });
+    }
+
+    if settings.mem {
+        print_memory_table(&mem_reports);
+    }
+
+    if settings.profile {
+        print_profile(&profile);
+    }
+
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {summary}");
+
+    let mut slow_parts = 0;
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
+        slow_parts = handle_baselines(year, &baseline, &timings);
+
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(year, timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -41,6 +124,218 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    if is_checked {
+        if regressions.is_empty() {
+            println!("\n{ANSI_BOLD}All recorded answers match.{ANSI_RESET}");
+        } else {
+            let days = regressions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            eprintln!("\n{ANSI_BOLD}Regressions in day(s):{ANSI_RESET} {days}");
+            process::exit(1);
+        }
+    }
+
+    if slow_parts > 0 {
+        eprintln!(
+            "\n{ANSI_BOLD}{slow_parts} part(s) regressed by more than {}%.{ANSI_RESET}",
+            baseline.threshold()
+        );
+        process::exit(1);
+    }
+
+    if summary.failed + summary.timed_out > 0 {
+        process::exit(1);
+    }
+}
+
+/// How a day ended, for the summary of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayOutcome {
+    Solved,
+    /// Not scaffolded yet, or a part returned no answer.
+    Unsolved,
+    /// A part panicked or regressed, or the solution could not be run.
+    Failed,
+    TimedOut,
+}
+
+impl DayOutcome {
+    fn from_reports(reports: &[PartReport], success: bool) -> Self {
+        if reports.iter().any(|r| r.failure == Some(Failure::TimedOut)) {
+            DayOutcome::TimedOut
+        } else if !success || reports.iter().any(PartReport::is_failure) {
+            DayOutcome::Failed
+        } else if reports.is_empty() || reports.iter().any(|r| !r.is_parse() && r.answer.is_none())
+        {
+            DayOutcome::Unsolved
+        } else {
+            DayOutcome::Solved
+        }
+    }
+}
+
+/// The number of days per outcome.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    solved: usize,
+    unsolved: usize,
+    failed: usize,
+    timed_out: usize,
+}
+
+impl Summary {
+    fn add(&mut self, outcome: DayOutcome) {
+        match outcome {
+            DayOutcome::Solved => self.solved += 1,
+            DayOutcome::Unsolved => self.unsolved += 1,
+            DayOutcome::Failed => self.failed += 1,
+            DayOutcome::TimedOut => self.timed_out += 1,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} solved, {} failed, {} timed out, {} unsolved",
+            self.solved, self.failed, self.timed_out, self.unsolved
+        )
+    }
+}
+
+/// Compare the timings to a baseline and/or save them as one. Returns the number
+/// of parts that regressed.
+fn handle_baselines(year: Year, args: &BaselineArgs, timings: &[Timings]) -> usize {
+    let current = baselines::from_timings(timings);
+    let mut slow_parts = 0;
+
+    if let Some(name) = &args.compare {
+        match baselines::load(year, name) {
+            Ok(baseline) => {
+                let deltas = baselines::compare(&baseline, &current);
+                slow_parts = baselines::print_comparison(name, &deltas, args.threshold());
+            }
+            Err(e) => {
+                eprintln!("Failed to load baseline \"{name}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if let Some(name) = &args.save {
+        match baselines::save(year, name, &current) {
+            Ok(path) => println!("Saved baseline to \"{path}\"."),
+            Err(e) => {
+                eprintln!("Failed to save baseline \"{name}\": {e}");
+            }
+        }
+    }
+
+    slow_parts
+}
+
+fn default_jobs(is_timed: bool) -> usize {
+    if is_timed {
+        1
+    } else {
+        thread::available_parallelism().map_or(1, usize::from)
+    }
+}
+
+/// Print the allocations of all parts that were run with `--mem`.
+fn print_memory_table(reports: &[PartReport]) {
+    println!("\n{ANSI_BOLD}Memory:{ANSI_RESET}");
+    println!("| Day | Part  | Allocations |  Allocated |       Peak |");
+    println!("| --: | :---- | ----------: | ---------: | ---------: |");
+
+    for report in reports {
+        let Some(mem) = report.mem else {
+            continue;
+        };
+        let part = if report.is_parse() {
+            "parse".to_string()
+        } else {
+            report.part.to_string()
+        };
+        println!(
+            "| {:>3} | {part:<5} | {:>11} | {:>10} | {:>10} |",
+            report.day.into_inner(),
+            mem.allocations,
+            format_bytes(mem.allocated_bytes),
+            format_bytes(mem.peak_bytes)
+        );
+    }
+}
+
+/// Print the spans of all parts, merging spans with the same path, e.g. the
+/// spans of a shared module that several days use.
+fn print_profile(profile: &Profile) {
+    println!("\n{ANSI_BOLD}Profile:{ANSI_RESET}");
+    if profile.spans.is_empty() {
+        println!("No spans were recorded.");
+    } else {
+        println!("{}", profile.format_tree(""));
+    }
+}
+
+fn print_header(day: Day) {
+    if day > 1 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run this command again with the `in_process` feature, which compiles the
+/// solutions into the library, and the features that `settings` need. Builds
+/// the same profile as this binary, and exits with the status of the new run.
+fn rerun_in_process(settings: RunSettings) -> ! {
+    let mut features = settings.features();
+    features.push("in_process".into());
+
+    let mut args: Vec<String> = vec![
+        "run".into(),
+        "--quiet".into(),
+        "--features".into(),
+        features.join(","),
+    ];
+
+    if !cfg!(debug_assertions) {
+        args.push("--release".into());
+    }
+
+    args.push("--".into());
+    args.extend(env::args().skip(1));
+
+    match Command::new("cargo").args(&args).status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run with the in_process feature: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Run the registered solution of a puzzle. The runner reads `--time`, `--check`, `--mem` and
+/// `--profile` from the arguments of this binary, and prints the results itself.
+fn run_in_process(puzzle: PuzzleId) -> Vec<PartReport> {
+    let Some(solution) = solution::get(puzzle) else {
+        return vec![];
+    };
+
+    match read_file("inputs", puzzle) {
+        Ok(input) => solution.run(&input),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            vec![]
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -56,199 +351,412 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost the output of the solution"),
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the
+/// records they print with `--format json`.
 mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use crate::template::{
+        report::{Failure, PartReport, PARSE_PART},
+        runner::{self, RunSettings},
+    };
+    use crate::{all_days, Day, PuzzleId, Year};
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, Stdio},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc::{self, RecvTimeoutError},
+        },
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// How long a solution bin may run past its `--timeout` before it is killed.
+    /// Solutions stop themselves once their time is up, so this only catches the
+    /// ones that can not, e.g. because they hang before the runner starts.
+    const KILL_GRACE: Duration = Duration::from_secs(5);
+
+    /// A line that a solution bin wrote to stdout.
+    enum Line {
+        Report(Box<PartReport>),
+        /// Anything that is not a record, e.g. debug output of a solution.
+        Text(String),
+    }
+
+    /// The buffered output of a solution bin.
+    #[derive(Default)]
+    pub struct Output {
+        stdout: Vec<Line>,
+        stderr: Vec<String>,
+        pub success: bool,
+    }
+
+    impl Output {
+        /// The output of a solution bin that could not be run.
+        fn from_error(error: &Error) -> Self {
+            Output {
+                stderr: vec![format!("Failed to run the solution: {error}")],
+                ..Output::default()
+            }
+        }
+
+        pub fn reports(&self) -> Vec<PartReport> {
+            self.stdout
+                .iter()
+                .filter_map(|line| match line {
+                    Line::Report(report) => Some(PartReport::clone(report)),
+                    Line::Text(_) => None,
+                })
+                .collect()
+        }
+
+        pub fn print(&self) {
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+
+            for line in &self.stdout {
+                match line {
+                    Line::Report(report) => runner::print_report(report),
+                    Line::Text(text) => println!("{text}"),
+                }
+            }
+        }
+    }
+
+    /// Run the solution bins of all days of a year, up to `jobs` at a time. `on_output`
+    /// is called for every day in order, as soon as that day and all days before it
+    /// have finished.
+    pub fn run_all(
+        year: Year,
+        jobs: usize,
+        settings: RunSettings,
+        is_release: bool,
+        mut on_output: impl FnMut(Day, Output),
+    ) {
+        if settings.timeout.is_some() {
+            build_all(settings, is_release);
+        }
+
+        let days: Vec<Day> = all_days().collect();
+        let next_day = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.clamp(1, days.len()) {
+                let (days, next_day, sender) = (&days, &next_day, sender.clone());
+
+                scope.spawn(move || loop {
+                    let idx = next_day.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(idx) else {
+                        break;
+                    };
+
+                    let output = run_solution(PuzzleId::new(year, day), settings, is_release);
+                    if sender.send((idx, output)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            // buffer results that finish out of order until it is their turn.
+            let mut pending = BTreeMap::new();
+            let mut next_print = 0;
+
+            for (idx, output) in receiver {
+                pending.insert(idx, output);
+
+                while let Some(output) = pending.remove(&next_print) {
+                    let output = output.unwrap_or_else(|e| Output::from_error(&e));
+                    on_output(days[next_print], output);
+                    next_print += 1;
+                }
+            }
+        });
+    }
+
+    /// Build all solution bins up front, so that their compile time does not count
+    /// towards the deadline of [`run_solution`]. Bins that fail to build fail
+    /// their day later on.
+    fn build_all(settings: RunSettings, is_release: bool) {
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--bins".into(),
+            "--keep-going".into(),
+        ];
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        let features = settings.features();
+        if !features.is_empty() {
+            args.extend(["--features".into(), features.join(",")]);
+        }
+
+        let _ = Command::new("cargo").args(&args).status();
+    }
+
+    /// Run the solution bin for a given puzzle and buffer its output. With a
+    /// `--timeout`, a bin that is still running [`KILL_GRACE`] after its time is
+    /// up is killed, and reported as timed out.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        settings: RunSettings,
+        is_release: bool,
+    ) -> Result<Output, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(Output {
+                success: true,
+                ..Output::default()
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            puzzle.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let features = settings.features();
+        if !features.is_empty() {
+            args.extend(["--features".into(), features.join(",")]);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.extend(["--".into(), "--format".into(), "json".into()]);
+
+        // mirror `--time`, `--check`, `--mem`, `--profile` and `--timeout` to child invocations.
+        args.extend(settings.to_args());
+
+        // spawn child command with piped stdout/stderr and buffer both, so
+        // that the output of concurrent days does not interleave.
 
-        let mut cmd = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        // killing `cargo run` does not stop the solution, but killing its
+        // process group does.
+        #[cfg(unix)]
+        if settings.timeout.is_some() {
+            std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        }
+
+        let mut cmd = command.spawn()?;
+        let deadline = settings
+            .timeout
+            .map(|timeout| (Instant::now() + timeout + KILL_GRACE, timeout));
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let thread = thread::spawn(move || stderr.lines().collect::<Result<Vec<_>, _>>());
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        // read stdout on a thread too, so that waiting for it can time out.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut output = Output::default();
+
+        loop {
+            let line = match deadline {
+                Some((deadline, timeout)) => {
+                    match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(line) => line,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Err(RecvTimeoutError::Timeout) => {
+                            kill(&mut cmd);
+                            let report = timed_out(puzzle, &output.reports(), timeout);
+                            output.stdout.push(Line::Report(Box::new(report)));
+                            return Ok(output);
+                        }
+                    }
+                }
+                None => match receiver.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                },
+            };
+
+            let line = line?;
+            output.stdout.push(match parse_report(&line) {
+                Some(report) => Line::Report(Box::new(report)),
+                None => Line::Text(line),
+            });
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        output.stderr = thread.join().map_err(|_| super::Error::BrokenPipe)??;
+        output.success = cmd.wait()?.success();
 
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
+    /// Kill a solution bin, together with the `cargo run` that started it.
+    fn kill(cmd: &mut Child) {
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-KILL", &format!("-{}", cmd.id())])
+            .status();
 
-        timings
+        let _ = cmd.kill();
+        let _ = cmd.wait();
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// The report of a killed solution bin, for the part after the last one that
+    /// it reported.
+    fn timed_out(puzzle: PuzzleId, reports: &[PartReport], timeout: Duration) -> PartReport {
+        PartReport {
+            day: puzzle.day,
+            part: reports.last().map_or(PARSE_PART, |report| report.part + 1),
+            answer: None,
+            duration: timeout,
+            stats: None,
+            mem: None,
+            verdict: None,
+            failure: Some(Failure::TimedOut),
+            profile: None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+    fn parse_report(line: &str) -> Option<PartReport> {
+        if !line.starts_with('{') {
+            return None;
+        }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        match PartReport::from_json(line) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                eprintln!("Could not parse record \"{line}\": {e}");
+                None
+            }
+        }
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::parse_report;
 
         use crate::day;
+        use crate::template::report::PartReport;
 
         #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn test_patterns_in_answer() {
+            let line = PartReport {
+                day: day!(1),
+                part: 1,
+                answer: Some("Part 2: (2s @ 5 samples)\n{".into()),
+                duration: Duration::from_nanos(10),
+                stats: None,
+                mem: None,
+                verdict: None,
+                failure: None,
+                profile: None,
+            }
+            .to_json();
+            let parsed = parse_report(&line).unwrap();
+            assert_eq!(parsed.answer.unwrap(), "Part 2: (2s @ 5 samples)\n{");
+            assert_eq!(
+                parse_report("Part 1: 0 (74.13ns @ 100000 samples)").is_none(),
+                true
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
+    }
+}
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DayOutcome, Summary};
+    use crate::day;
+    use crate::template::report::{Failure, PartReport};
+
+    fn report(part: u8, answer: Option<&str>, failure: Option<Failure>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: answer.map(String::from),
+            duration: Duration::from_millis(1),
+            stats: None,
+            mem: None,
+            verdict: None,
+            failure,
+            profile: None,
         }
+    }
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn determines_day_outcomes() {
+        let solved = [report(1, Some("1"), None), report(2, Some("2"), None)];
+        assert_eq!(DayOutcome::from_reports(&solved, true), DayOutcome::Solved);
+        assert_eq!(DayOutcome::from_reports(&solved, false), DayOutcome::Failed);
+        assert_eq!(DayOutcome::from_reports(&[], true), DayOutcome::Unsolved);
+        assert_eq!(
+            DayOutcome::from_reports(&[report(0, None, None), report(1, None, None)], true),
+            DayOutcome::Unsolved
+        );
+
+        let panicked = report(1, None, Some(Failure::Panicked("oops".into())));
+        assert_eq!(
+            DayOutcome::from_reports(&[panicked.clone()], false),
+            DayOutcome::Failed
+        );
+        assert_eq!(
+            DayOutcome::from_reports(&[panicked, report(2, None, Some(Failure::TimedOut))], false),
+            DayOutcome::TimedOut
+        );
+    }
+
+    #[test]
+    fn summarizes_outcomes() {
+        let mut summary = Summary::default();
+        [
+            DayOutcome::Solved,
+            DayOutcome::Solved,
+            DayOutcome::Failed,
+            DayOutcome::TimedOut,
+            DayOutcome::Unsolved,
+        ]
+        .into_iter()
+        .for_each(|outcome| summary.add(outcome));
+        assert_eq!(
+            summary.to_string(),
+            "2 solved, 1 failed, 1 timed out, 1 unsolved"
+        );
     }
 }
@@ -0,0 +1,43 @@
+use std::process;
+
+use crate::template::examples::Examples;
+use crate::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId) {
+    let examples = match Examples::read(puzzle) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!(
+                "failed to read the description of puzzle {puzzle}: {e}. Download it with `cargo download {} --year {}`",
+                puzzle.day, puzzle.year
+            );
+            process::exit(1);
+        }
+    };
+
+    if examples.inputs.is_empty() {
+        println!("No examples found in the description of puzzle {puzzle}.");
+    }
+
+    match examples.write(puzzle) {
+        Ok(paths) => {
+            for path in paths {
+                println!("🎄 Extracted example file \"{path}\".");
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to write the examples of puzzle {puzzle}: {e}");
+            process::exit(1);
+        }
+    }
+
+    for (part, answer) in (1..).zip(&examples.answers) {
+        if let Some(answer) = answer {
+            println!(
+                "Part {part}: {} (example \"{}\")",
+                answer.value,
+                examples.file_name(puzzle.day, answer.example)
+            );
+        }
+    }
+}
@@ -1,5 +1,8 @@
 pub mod all;
 pub mod download;
+pub mod extract;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod watch;
@@ -0,0 +1,48 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{
+    ledger::Ledger,
+    progress::{self, DayProgress},
+};
+use crate::{PuzzleId, Year};
+
+/// Show the progress of a year in the terminal and update the stars table in
+/// the readme. With `run_tests`, the tests of every solution are run as well.
+pub fn handle(year: Year, run_tests: bool) {
+    let ledger = match Ledger::load(year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Failed to read the submissions of {year}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut days = progress::collect(year, &ledger);
+
+    if run_tests {
+        for progress in days.iter_mut().filter(|p| p.has_solution) {
+            progress.tests = Some(run_tests_of(year, progress));
+        }
+    }
+
+    print!("{}", progress::calendar(year, &days));
+
+    match progress::update(year, &days) {
+        Ok(()) => println!("Successfully updated README with stars."),
+        Err(_) => {
+            eprintln!("Failed to update readme with stars.");
+        }
+    }
+}
+
+fn run_tests_of(year: Year, progress: &DayProgress) -> bool {
+    let puzzle = PuzzleId::new(year, progress.day);
+    eprintln!("Testing {puzzle}...");
+
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
@@ -1,18 +1,30 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{config, examples::Examples};
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// The directory with user templates, which take precedence over the built-in
+/// templates below. E.g. `templates/bin.rs` replaces [`MODULE_TEMPLATE`].
+const TEMPLATES_DIR: &str = "templates";
 
-pub fn part_one(input: &str) -> Option<u32> {
+const MODULE_TEMPLATE: &str = r#"use advent_of_code::puzzles::yYEAR_NUMBER::dDAY_PADDED::Puzzle;
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse);
+
+pub fn parse(input: &str) -> Puzzle {
+    input.parse().unwrap()
+}
+
+pub fn part_one(puzzle: &Puzzle) -> Option<u32> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(puzzle: &Puzzle) -> Option<u32> {
     None
 }
 
@@ -22,30 +34,166 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&parse(
+            &advent_of_code::template::EXAMPLE_PART_ONE.unwrap(),
+        ));
+        assert_eq!(result, ANSWER_PART_ONE);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&parse(
+            &advent_of_code::template::EXAMPLE_PART_TWO.unwrap(),
+        ));
+        assert_eq!(result, ANSWER_PART_TWO);
+    }
+}
+"#;
+
+const PUZZLE_MODULE_TEMPLATE: &str = r#"use std::{error::Error, str::FromStr};
+
+pub struct Puzzle {}
+
+impl FromStr for Puzzle {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Puzzle {})
     }
 }
 "#;
 
+/// Read a user template from [`TEMPLATES_DIR`], falling back to the built-in one.
+fn read_template(name: &str, default: &str) -> String {
+    fs::read_to_string(Path::new(TEMPLATES_DIR).join(name)).unwrap_or_else(|_| default.into())
+}
+
+/// Fill in a template, reading the examples and expecting the answers that
+/// were found in the puzzle description.
+fn fill_template(template: &str, puzzle: PuzzleId, examples: &Examples) -> String {
+    let mut contents = template
+        .replace("YEAR_NUMBER", &puzzle.year.to_string())
+        .replace("DAY_PADDED", &puzzle.day.to_string())
+        .replace("DAY_NUMBER", &puzzle.day.into_inner().to_string());
+
+    for (i, name) in ["ONE", "TWO"].into_iter().enumerate() {
+        let answer = examples.answers[i].as_ref();
+        // with several examples, there is no `NN.txt`, so a part without an
+        // answer falls back to the first example.
+        let example = if examples.inputs.len() > 1 {
+            format!(
+                "read_file_part(\"examples\", PUZZLE, {})",
+                answer.map_or(0, |answer| answer.example) + 1
+            )
+        } else {
+            "read_file(\"examples\", PUZZLE)".to_string()
+        };
+        // the template returns an `Option<u32>`, leave other answers to the user.
+        let expected = match answer.and_then(|a| a.value.parse::<u32>().ok()) {
+            Some(value) => format!("Some({value})"),
+            None => "None".to_string(),
+        };
+
+        contents = contents
+            .replace(&format!("EXAMPLE_PART_{name}"), &example)
+            .replace(&format!("ANSWER_PART_{name}"), &expected);
+    }
+
+    contents
+}
+
+/// Add `pub mod {name};` to the contents of a `mod.rs` file, keeping the
+/// declarations sorted. Returns [`None`] if the module is already declared.
+fn declare_module(contents: &str, name: &str) -> Option<String> {
+    let declaration = format!("pub mod {name};");
+    if contents.lines().any(|l| l.trim() == declaration) {
+        return None;
+    }
+
+    let mut lines: Vec<&str> = contents.lines().collect();
+    lines.push(&declaration);
+    if lines.iter().all(|l| l.starts_with("pub mod ")) {
+        lines.sort_unstable();
+    }
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn register_module(mod_path: &Path, name: &str) -> Result<(), std::io::Error> {
+    let contents = fs::read_to_string(mod_path).unwrap_or_default();
+    match declare_module(&contents, name) {
+        Some(contents) => fs::write(mod_path, contents),
+        None => Ok(()),
+    }
+}
+
+/// Create the library module of a puzzle, i.e. `puzzles::y2023::d08`, unless it
+/// exists already.
+fn create_puzzle_module(puzzle: PuzzleId, examples: &Examples) {
+    let year_dir = Path::new("src/puzzles").join(format!("y{}", puzzle.year));
+    let path = year_dir.join(format!("d{}.rs", puzzle.day));
+
+    if let Err(e) = fs::create_dir_all(&year_dir) {
+        eprintln!("Failed to create puzzles folder: {e}");
+        process::exit(1);
+    }
+
+    match safe_create_file(&path.to_string_lossy()) {
+        Ok(mut file) => {
+            let template = read_template("puzzle.rs", PUZZLE_MODULE_TEMPLATE);
+            if let Err(e) = file.write_all(fill_template(&template, puzzle, examples).as_bytes()) {
+                eprintln!("Failed to write puzzle module contents: {e}");
+                process::exit(1);
+            }
+            println!("Created puzzle module file \"{}\"", path.display());
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing puzzle module file \"{}\"", path.display());
+        }
+        Err(e) => {
+            eprintln!("Failed to create puzzle module file: {e}");
+            process::exit(1);
+        }
+    }
+
+    let registered = register_module(&year_dir.join("mod.rs"), &format!("d{}", puzzle.day))
+        .and_then(|()| {
+            register_module(
+                Path::new("src/puzzles/mod.rs"),
+                &format!("y{}", puzzle.year),
+            )
+        });
+    if let Err(e) = registered {
+        eprintln!("Failed to register puzzle module: {e}");
+        process::exit(1);
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId) {
+    let PuzzleId { year, day } = puzzle;
+    let data = config::get().year_dir(year);
+    let input_path = format!("{data}/inputs/{day}.txt");
+    let example_path = format!("{data}/examples/{day}.txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    for folder in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(format!("{data}/{folder}")) {
+            eprintln!("Failed to create data folder: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,11 +203,11 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    // the puzzle description is only there if the day was downloaded first.
+    let examples = Examples::read(puzzle).unwrap_or_default();
+
+    let template = read_template("bin.rs", MODULE_TEMPLATE);
+    match file.write_all(fill_template(&template, puzzle, &examples).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -69,6 +217,8 @@ pub fn handle(day: Day) {
         }
     }
 
+    create_puzzle_module(puzzle, &examples);
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -79,16 +229,119 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if examples.inputs.is_empty() {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    } else {
+        match examples.write(puzzle) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("Extracted example file \"{path}\"");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write example files: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{declare_module, fill_template, MODULE_TEMPLATE, PUZZLE_MODULE_TEMPLATE};
+    use crate::template::examples::{Answer, Examples};
+    use crate::{day, year, PuzzleId};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(8));
+
+    #[test]
+    fn fills_in_template() {
+        let contents = fill_template(MODULE_TEMPLATE, PUZZLE, &Examples::default());
+        assert!(contents.starts_with("use advent_of_code::puzzles::y2023::d08::Puzzle;"));
+        assert!(contents.contains("advent_of_code::solution!(2023, 8, parse);"));
+        assert!(contents.contains("read_file(\"examples\", PUZZLE).unwrap(),"));
+        assert_eq!(contents.matches("assert_eq!(result, None);").count(), 2);
+    }
+
+    #[test]
+    fn fills_in_examples() {
+        let examples = Examples {
+            inputs: vec!["RL\n".into(), "LLR\n".into()],
+            answers: [
+                Some(Answer {
+                    value: "2".into(),
+                    example: 0,
+                }),
+                Some(Answer {
+                    value: "11Z".into(),
+                    example: 1,
+                }),
+            ],
+        };
+        let contents = fill_template(MODULE_TEMPLATE, PUZZLE, &examples);
+        assert!(
+            contents.contains("&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 1)")
+        );
+        assert!(contents.contains("assert_eq!(result, Some(2));"));
+        assert!(
+            contents.contains("&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2)")
+        );
+        assert!(contents.contains("assert_eq!(result, None);"));
+    }
+
+    #[test]
+    fn falls_back_to_first_example() {
+        let examples = Examples {
+            inputs: vec!["RL\n".into(), "LLR\n".into()],
+            answers: [
+                Some(Answer {
+                    value: "6".into(),
+                    example: 1,
+                }),
+                None,
+            ],
+        };
+        let contents = fill_template(MODULE_TEMPLATE, PUZZLE, &examples);
+        assert!(
+            contents.contains("&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2)")
+        );
+        assert!(
+            contents.contains("&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 1)")
+        );
+        assert!(!contents.contains("read_file(\"examples\", PUZZLE)"));
+    }
+
+    #[test]
+    fn fills_in_puzzle_module() {
+        let contents = fill_template(PUZZLE_MODULE_TEMPLATE, PUZZLE, &Examples::default());
+        assert!(contents.contains("impl FromStr for Puzzle {"));
+    }
+
+    #[test]
+    fn declares_modules() {
+        assert_eq!(
+            declare_module("pub mod d03;\npub mod d17;\n", "d08").as_deref(),
+            Some("pub mod d03;\npub mod d08;\npub mod d17;\n")
+        );
+        assert_eq!(declare_module("pub mod d08;\n", "d08"), None);
+        assert_eq!(
+            declare_module("", "y2024").as_deref(),
+            Some("pub mod y2024;\n")
+        );
+        assert_eq!(
+            declare_module("pub mod y2023;\n\nmod private;\n", "y2024").as_deref(),
+            Some("pub mod y2023;\n\nmod private;\npub mod y2024;\n")
+        );
+    }
 }
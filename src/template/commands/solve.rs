@@ -1,31 +1,86 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::{commands::watch, report::OutputFormat, runner::RunSettings};
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// The options of `solve` that, unlike [`RunSettings`], do not apply to `all`.
+#[derive(Debug, Default)]
+pub struct SolveArgs {
+    pub format: Option<OutputFormat>,
+    /// The part to submit.
+    pub submit: Option<u8>,
+    /// A path to read the input from, or `-` for stdin.
+    pub input: Option<String>,
+    /// Parameter overrides, e.g. `steps=5000`.
+    pub params: Vec<String>,
+    /// Rerun the solution whenever its files change.
+    pub watch: bool,
+}
+
+pub fn handle(puzzle: PuzzleId, release: bool, settings: RunSettings, args: SolveArgs) {
+    if args.watch {
+        watch::handle(puzzle, release, settings, args);
+        return;
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(build_args(puzzle, release, settings, &args))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// The arguments of the `cargo run` invocation of a solution.
+#[must_use]
+pub fn build_args(
+    puzzle: PuzzleId,
+    release: bool,
+    settings: RunSettings,
+    args: &SolveArgs,
+) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    let features = settings.features();
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = args.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    cmd_args.extend(settings.to_args());
+
+    // the solution inherits stdin, so `--input -` is read by the solution itself.
+    if let Some(input) = &args.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.clone());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    if let Some(format) = args.format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    for param in &args.params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
 
-    cmd.wait().unwrap();
+    cmd_args
 }
@@ -0,0 +1,270 @@
+/// Reruns the solution of a puzzle whenever its sources or data files change,
+/// for `cargo solve <day> --watch`.
+///
+/// Files are polled for changes of their modification time, so this works
+/// without a file notification service on every platform.
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    commands::solve::{build_args, SolveArgs},
+    config,
+    report::{OutputFormat, PartReport},
+    runner::{self, RunSettings},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of the watched files. Files that do not exist are
+/// included too, so that creating them counts as a change.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+pub fn handle(puzzle: PuzzleId, release: bool, settings: RunSettings, args: SolveArgs) {
+    if args.submit.is_some() {
+        eprintln!("--watch can not be combined with --submit.");
+        process::exit(1);
+    }
+
+    if args.input.as_deref() == Some("-") {
+        eprintln!("--watch can not read the input from stdin.");
+        process::exit(1);
+    }
+
+    let args = SolveArgs {
+        format: Some(OutputFormat::Json),
+        ..args
+    };
+    let cmd_args = build_args(puzzle, release, settings, &args);
+    // new files, e.g. the examples of `cargo extract`, are picked up between
+    // polls.
+    let paths = || watched_paths(puzzle, args.input.as_deref());
+
+    let mut previous: Vec<PartReport> = vec![];
+    let mut snapshot = take_snapshot(&paths());
+
+    loop {
+        // clear the screen and move the cursor to the top left.
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{ANSI_BOLD}Watching {puzzle}{ANSI_RESET} {ANSI_ITALIC}(Ctrl-C to stop){ANSI_RESET}"
+        );
+        println!();
+
+        let reports = run(&cmd_args);
+
+        if !previous.is_empty() && !reports.is_empty() {
+            println!("\n{ANSI_BOLD}Compared to the previous run:{ANSI_RESET}");
+            for report in &reports {
+                if let Some(before) = previous.iter().find(|r| r.part == report.part) {
+                    println!("{}", format_change(before, report));
+                }
+            }
+        }
+
+        if !reports.is_empty() {
+            previous = reports;
+        }
+
+        snapshot = wait_for_change(paths, snapshot);
+    }
+}
+
+/// The sources and data files of a puzzle, and the modules shared by all
+/// puzzles, sorted.
+fn watched_paths(puzzle: PuzzleId, input: Option<&str>) -> Vec<PathBuf> {
+    let PuzzleId { year, day } = puzzle;
+    let data = config::get().year_dir(year);
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{puzzle}.rs")),
+        PathBuf::from(format!("src/puzzles/y{year}/d{day}.rs")),
+        PathBuf::from(format!("{data}/answers/{day}.txt")),
+    ];
+
+    match input {
+        Some(input) => paths.push(PathBuf::from(input)),
+        None => paths.push(PathBuf::from(format!("{data}/inputs/{day}.txt"))),
+    }
+
+    // examples can be split into one file per part, e.g. `17-2.txt`.
+    let examples = PathBuf::from(format!("{data}/examples"));
+    let example_files = fs::read_dir(&examples).into_iter().flatten().flatten();
+    for entry in example_files {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name == format!("{day}.txt") || name.starts_with(&format!("{day}-")) {
+            paths.push(entry.path());
+        }
+    }
+
+    // e.g. `src/puzzles/grid.rs` and `src/puzzles/graph/mod.rs`, but not the
+    // solutions of other years.
+    let shared = fs::read_dir("src/puzzles").into_iter().flatten().flatten();
+    for entry in shared {
+        let path = entry.path();
+        if path.is_dir() && !entry.file_name().to_string_lossy().starts_with('y') {
+            let files = fs::read_dir(&path).into_iter().flatten().flatten();
+            paths.extend(files.map(|e| e.path()).filter(|p| is_rust_file(p)));
+        } else if is_rust_file(&path) {
+            paths.push(path);
+        }
+    }
+
+    // the order of read_dir is unspecified, it should not count as a change.
+    paths.sort();
+    paths
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "rs")
+}
+
+fn take_snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| (path.clone(), modified(path)))
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Poll the watched files until one of them changes or a file starts being
+/// watched, and return their new state.
+fn wait_for_change(paths: impl Fn() -> Vec<PathBuf>, snapshot: Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = take_snapshot(&paths());
+        if current != snapshot {
+            // editors may write a file in several steps, let them finish.
+            thread::sleep(POLL_INTERVAL / 5);
+            return take_snapshot(&paths());
+        }
+    }
+}
+
+/// Build and run the solution, printing its output like `solve` does. Returns
+/// the reports of the parts, which are empty if the build failed.
+fn run(cmd_args: &[String]) -> Vec<PartReport> {
+    let cmd = Command::new("cargo")
+        .args(cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn();
+
+    let mut cmd = match cmd {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut reports = vec![];
+
+    if let Some(stdout) = cmd.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let report = if line.starts_with('{') {
+                PartReport::from_json(&line).ok()
+            } else {
+                None
+            };
+
+            match report {
+                Some(report) => {
+                    runner::print_report(&report);
+                    reports.push(report);
+                }
+                None => println!("{line}"),
+            }
+        }
+    }
+
+    let _ = cmd.wait();
+    reports
+}
+
+/// Describe how the answer and timing of a part changed between two runs.
+fn format_change(before: &PartReport, after: &PartReport) -> String {
+    let name = if after.is_parse() {
+        "Parse".to_string()
+    } else {
+        format!("Part {}", after.part)
+    };
+    let timing = format!("{:.1?} → {:.1?}", before.duration, after.duration);
+
+    if after.is_parse() {
+        return format!("{name}: {timing}");
+    }
+
+    let answer = |report: &PartReport| report.answer.clone().unwrap_or_else(|| "✖".into());
+    if before.answer == after.answer {
+        format!("{name}: {} (unchanged) · {timing}", answer(after))
+    } else {
+        format!(
+            "{name}: {ANSI_BOLD}{} → {}{ANSI_RESET} · {timing}",
+            answer(before),
+            answer(after)
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_change, watched_paths};
+    use crate::template::{report::PartReport, ANSI_BOLD, ANSI_RESET};
+    use crate::{day, year, PuzzleId};
+
+    fn report(part: u8, answer: Option<&str>, millis: u64) -> PartReport {
+        PartReport {
+            day: day!(17),
+            part,
+            answer: answer.map(String::from),
+            duration: Duration::from_millis(millis),
+            stats: None,
+            mem: None,
+            verdict: None,
+            failure: None,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn formats_changes() {
+        assert_eq!(
+            format_change(&report(1, Some("42"), 10), &report(1, Some("42"), 5)),
+            "Part 1: 42 (unchanged) · 10.0ms → 5.0ms"
+        );
+        assert_eq!(
+            format_change(&report(2, None, 10), &report(2, Some("7"), 10)),
+            format!("Part 2: {ANSI_BOLD}✖ → 7{ANSI_RESET} · 10.0ms → 10.0ms")
+        );
+        assert_eq!(
+            format_change(&report(0, None, 2), &report(0, None, 1)),
+            "Parse: 2.0ms → 1.0ms"
+        );
+    }
+
+    #[test]
+    fn watches_sources_and_data() {
+        let paths = watched_paths(PuzzleId::new(year!(2023), day!(17)), None);
+        let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        assert!(paths.contains(&"src/bin/2023-17.rs".into()));
+        assert!(paths.contains(&"src/puzzles/y2023/d17.rs".into()));
+        assert!(paths.contains(&"data/2023/inputs/17.txt".into()));
+        assert!(paths.contains(&"data/2023/examples/17-2.txt".into()));
+        assert!(paths.contains(&"src/puzzles/grid.rs".into()));
+        assert!(paths.contains(&"src/puzzles/graph/union_find.rs".into()));
+        assert!(paths.is_sorted());
+    }
+}
@@ -0,0 +1,512 @@
+/// Module that reads the configuration of the project. Settings come in layers:
+/// the built-in defaults, then the config file `aoc.toml` in the project root
+/// (or the file in `AOC_CONFIG`), then environment variables, and finally the
+/// command-line flags, which the commands apply on top. Only the bench settings
+/// and the parameters have flags.
+///
+/// The config file is a small subset of TOML:
+///
+/// ```toml
+/// data_dir = "data"
+///
+/// [bench]
+/// time = "5s"
+/// samples = 100
+///
+/// [submit]
+/// backend = "aoc-cli"   # "auto", "native" or "aoc-cli"
+/// base_url = "https://adventofcode.com"
+/// contact = "you@example.com"
+///
+/// [params.2023-02]
+/// red = 12
+/// ```
+///
+/// Each setting has an environment variable, see [`ENV_VARS`]. Parameters are
+/// set like `AOC_PARAM_2023_02_RED=20`. Unknown keys are reported, but do not
+/// stop the run. The session token is a secret, so it is not part of the
+/// config, see [`super::aoc_client`].
+use std::{env, fmt::Display, fs, io, process, str::FromStr, time::Duration};
+
+use lazy_static::lazy_static;
+
+use crate::template::{aoc_client::DEFAULT_BASE_URL, json::Value, parse_duration};
+use crate::{PuzzleId, Year};
+
+/// The config file that is read unless `AOC_CONFIG` points elsewhere.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// The environment variables of the settings, by key.
+pub const ENV_VARS: [(&str, &str); 6] = [
+    ("AOC_DATA_DIR", "data_dir"),
+    ("AOC_BENCH_TIME", "bench.time"),
+    ("AOC_BENCH_SAMPLES", "bench.samples"),
+    ("AOC_BACKEND", "submit.backend"),
+    ("AOC_BASE_URL", "submit.base_url"),
+    ("AOC_CONTACT", "submit.contact"),
+];
+
+/// The prefix of the environment variables that set parameters.
+const PARAM_PREFIX: &str = "AOC_PARAM_";
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Syntax { line: usize, message: String },
+    Value { key: String, value: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            Error::Value { key, value } => write!(f, "invalid value \"{value}\" for `{key}`"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Which backend talks to the website, see [`super::aoc_client::Backend`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubmitBackend {
+    /// The native client if a session token is configured, `aoc-cli` otherwise.
+    #[default]
+    Auto,
+    Native,
+    Cli,
+}
+
+impl FromStr for SubmitBackend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(SubmitBackend::Auto),
+            "native" => Ok(SubmitBackend::Native),
+            "aoc-cli" => Ok(SubmitBackend::Cli),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// The time budget of a benchmark, see `--bench-time`.
+    pub time: Option<Duration>,
+    /// A fixed number of samples, see `--samples`.
+    pub samples: Option<u128>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitConfig {
+    pub backend: SubmitBackend,
+    pub base_url: String,
+    /// Who the website can contact about the requests, e.g. an email address.
+    pub contact: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The folder with a subfolder of inputs, examples, answers, ... per year.
+    pub data_dir: String,
+    pub bench: BenchConfig,
+    pub submit: SubmitConfig,
+    /// The parameters of the solutions, in the order they were set.
+    pub params: Vec<(PuzzleId, String, String)>,
+    /// The keys that were not recognised, e.g. `bench.sample`.
+    pub unknown_keys: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            data_dir: "data".into(),
+            bench: BenchConfig::default(),
+            submit: SubmitConfig {
+                backend: SubmitBackend::Auto,
+                base_url: DEFAULT_BASE_URL.into(),
+                contact: None,
+            },
+            params: vec![],
+            unknown_keys: vec![],
+        }
+    }
+}
+
+impl Config {
+    /// Layer a config file and environment variables over the defaults.
+    pub fn layered(
+        file: Option<&str>,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, Error> {
+        let mut config = Config::default();
+
+        if let Some(contents) = file {
+            for (key, value) in parse(contents)? {
+                config.set(&key, &value_text(&value))?;
+            }
+        }
+
+        for (name, value) in vars {
+            if let Some((_, key)) = ENV_VARS.iter().find(|(var, _)| *var == name) {
+                config
+                    .set(key, &value)
+                    .map_err(|_| Error::Value { key: name, value })?;
+            } else if let Some(rest) = name.strip_prefix(PARAM_PREFIX) {
+                match param_key(rest) {
+                    Some(key) => config.set(&key, &value)?,
+                    None => config.unknown_keys.push(name),
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Apply a setting, noting the key if it is unknown.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let invalid = || Error::Value {
+            key: key.into(),
+            value: value.into(),
+        };
+
+        match key {
+            "data_dir" => self.data_dir = value.trim_end_matches('/').into(),
+            "bench.time" => self.bench.time = Some(parse_duration(value).map_err(|_| invalid())?),
+            "bench.samples" => {
+                let samples = value.parse().ok().filter(|&n| n > 0);
+                self.bench.samples = Some(samples.ok_or_else(invalid)?);
+            }
+            "submit.backend" => self.submit.backend = value.parse().map_err(|()| invalid())?,
+            "submit.base_url" => self.submit.base_url = value.trim_end_matches('/').into(),
+            "submit.contact" => self.submit.contact = Some(value.into()),
+            _ => {
+                let param = key.strip_prefix("params.").and_then(|rest| {
+                    let (puzzle, name) = rest.split_once('.')?;
+                    Some((puzzle.parse().ok()?, name))
+                });
+                match param {
+                    Some((puzzle, name)) => {
+                        self.params.push((puzzle, name.into(), value.into()));
+                    }
+                    None => self.unknown_keys.push(key.into()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The parameters that are configured for a puzzle.
+    pub fn params(&self, puzzle: PuzzleId) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .filter(move |(p, _, _)| *p == puzzle)
+            .map(|(_, name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The data folder of a year, e.g. `data/2023`.
+    #[must_use]
+    pub fn year_dir(&self, year: Year) -> String {
+        format!("{}/{year}", self.data_dir)
+    }
+}
+
+/// The key of a parameter from the rest of its environment variable, e.g.
+/// `params.2023-02.red` from `2023_02_RED`.
+fn param_key(var: &str) -> Option<String> {
+    let mut parts = var.splitn(3, '_');
+    let (year, day, name) = (parts.next()?, parts.next()?, parts.next()?);
+    let puzzle: PuzzleId = format!("{year}-{day}").parse().ok()?;
+    Some(format!("params.{puzzle}.{}", name.to_lowercase()))
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Parse a config file into its dotted keys and values. Supports tables, bare
+/// keys, strings, numbers and booleans, which is all the config needs.
+pub fn parse(contents: &str) -> Result<Vec<(String, Value)>, Error> {
+    let mut entries: Vec<(String, Value)> = vec![];
+    let mut table = String::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let syntax = |message: &str| Error::Syntax {
+            line: i + 1,
+            message: message.into(),
+        };
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| syntax("expecting a table like `[bench]`"))?;
+            table = parse_key(header).ok_or_else(|| syntax("invalid table name"))?;
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax("expecting `key = value`"))?;
+        let key = parse_key(key).ok_or_else(|| syntax("invalid key"))?;
+        let key = if table.is_empty() {
+            key
+        } else {
+            format!("{table}.{key}")
+        };
+        let value = parse_value(value.trim()).ok_or_else(|| syntax("unsupported value"))?;
+
+        if entries.iter().any(|(k, _)| *k == key) {
+            return Err(syntax(&format!("duplicate key `{key}`")));
+        }
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+/// A dotted key of bare words, e.g. `params.2023-02`.
+fn parse_key(s: &str) -> Option<String> {
+    let parts: Vec<&str> = s.split('.').map(str::trim).collect();
+    parts
+        .iter()
+        .all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .then(|| parts.join("."))
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    match s {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+
+    if let Some(literal) = s.strip_prefix('\'') {
+        let literal = literal.strip_suffix('\'')?;
+        return (!literal.contains('\'')).then(|| Value::String(literal.into()));
+    }
+
+    if let Some(quoted) = s.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return chars.as_str().is_empty().then_some(Value::String(value)),
+                '\\' => value.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c @ ('"' | '\\') => c,
+                    _ => return None,
+                }),
+                c => value.push(c),
+            }
+        }
+        return None;
+    }
+
+    let number = s.replace('_', "");
+    (number.parse::<i128>().is_ok() || number.parse::<f64>().is_ok())
+        .then_some(Value::Number(number))
+}
+
+/// Remove a comment from a line, unless the `#` is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+/// Read the config of the project from its file and the environment.
+pub fn load() -> Result<Config, Error> {
+    let contents = match env::var("AOC_CONFIG") {
+        Ok(path) => Some(fs::read_to_string(path)?),
+        Err(_) => match fs::read_to_string(CONFIG_FILE) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        },
+    };
+
+    Config::layered(contents.as_deref(), env::vars())
+}
+
+lazy_static! {
+    static ref CONFIG: Config = load().unwrap_or_else(|e| {
+        eprintln!("Error: invalid config: {e}");
+        process::exit(1);
+    });
+}
+
+/// The config of the project. Exits if it is invalid.
+pub fn get() -> &'static Config {
+    &CONFIG
+}
+
+/// Warn about the keys of the config that are not recognised.
+pub fn report_unknown_keys() {
+    for key in &get().unknown_keys {
+        eprintln!("Warning: unknown config key `{key}`, it is ignored.");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, Config, Error, SubmitBackend};
+    use crate::template::json::Value;
+    use crate::{day, year, PuzzleId};
+
+    const FILE: &str = r##"
+# the project config
+data_dir = "puzzle data/"   # trailing slashes are dropped
+
+[bench]
+time = '2.5s'
+samples = 1_000
+
+[submit]
+backend = "aoc-cli"
+
+[params.2023-02]
+red = 20
+blue = 3
+colour = "#00f"
+"##;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_files() {
+        let entries = parse(FILE).unwrap();
+        assert_eq!(
+            entries[0],
+            ("data_dir".into(), Value::String("puzzle data/".into()))
+        );
+        assert_eq!(
+            entries[2],
+            ("bench.samples".into(), Value::Number("1000".into()))
+        );
+        assert_eq!(
+            entries[6],
+            ("params.2023-02.colour".into(), Value::String("#00f".into()))
+        );
+
+        assert!(matches!(
+            parse("[bench]\ntime = 1s"),
+            Err(Error::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("a = 1\na = 2"),
+            Err(Error::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("[bench"),
+            Err(Error::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn layers_settings() {
+        assert_eq!(Config::layered(None, vec![]).unwrap(), Config::default());
+
+        let config = Config::layered(
+            Some(FILE),
+            vars(&[
+                ("AOC_BENCH_SAMPLES", "10"),
+                ("AOC_PARAM_2023_02_RED", "30"),
+                ("AOC_CONTACT", "me@example.com"),
+                ("HOME", "/root"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.data_dir, "puzzle data");
+        assert_eq!(config.year_dir(year!(2023)), "puzzle data/2023");
+        assert_eq!(config.bench.time, Some(Duration::from_millis(2500)));
+        assert_eq!(config.bench.samples, Some(10));
+        assert_eq!(config.submit.backend, SubmitBackend::Cli);
+        assert_eq!(config.submit.contact.as_deref(), Some("me@example.com"));
+
+        let puzzle = PuzzleId::new(year!(2023), day!(2));
+        assert_eq!(
+            config.params(puzzle).collect::<Vec<_>>(),
+            vec![
+                ("red", "20"),
+                ("blue", "3"),
+                ("colour", "#00f"),
+                ("red", "30")
+            ]
+        );
+        assert_eq!(
+            config.params(PuzzleId::new(year!(2023), day!(3))).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_and_invalid_values() {
+        let config = Config::layered(
+            Some("[bench]\nsample = 10\n[params]\nred = 1"),
+            vars(&[("AOC_PARAM_RED", "1")]),
+        )
+        .unwrap();
+        assert_eq!(
+            config.unknown_keys,
+            vec!["bench.sample", "params.red", "AOC_PARAM_RED"]
+        );
+
+        let error = Config::layered(None, vars(&[("AOC_BACKEND", "curl")])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid value \"curl\" for `AOC_BACKEND`"
+        );
+        assert!(Config::layered(Some("[bench]\ntime = \"soon\""), vec![]).is_err());
+
+        let error = Config::layered(None, vars(&[("AOC_BENCH_SAMPLES", "0")])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid value \"0\" for `AOC_BENCH_SAMPLES`"
+        );
+        assert!(Config::layered(Some("[bench]\nsamples = 0"), vec![]).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+/// Extracts the example inputs and their expected answers from the markdown
+/// description of a puzzle, as saved by `cargo download`.
+///
+/// This is a heuristic, so check what it found: a code block counts as an
+/// example input when the line before it introduces an example, and the last
+/// emphasised value of a part, e.g. `<em><code>142</code></em>`, counts as the
+/// answer of that part for the latest example.
+use std::{fs, io};
+
+use crate::template::{aoc_cli::get_puzzle_path, config};
+use crate::{Day, PuzzleId};
+
+/// The expected answer of a part for one of the examples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub value: String,
+    /// Index into [`Examples::inputs`].
+    pub example: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub inputs: Vec<String>,
+    pub answers: [Option<Answer>; 2],
+}
+
+impl Examples {
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let mut examples = Self::default();
+        let mut part = 0;
+        let mut previous_line = "";
+        let mut block: Option<Vec<&str>> = None;
+        let mut pending_answer = false;
+
+        for line in markdown.lines() {
+            if let Some(lines) = &mut block {
+                if line.trim_end() != "```" {
+                    lines.push(line);
+                    continue;
+                }
+
+                let is_example = introduces_example(previous_line);
+                let lines = block.take().unwrap_or_default();
+                if is_example {
+                    let index = examples.push_input(&lines);
+                    // an answer stated before an example belongs to it.
+                    if pending_answer {
+                        if let Some(answer) = &mut examples.answers[part] {
+                            answer.example = index;
+                        }
+                        pending_answer = false;
+                    }
+                }
+                continue;
+            }
+
+            if line.trim_end() == "```" {
+                block = Some(vec![]);
+                continue;
+            }
+
+            if line.contains("--- Part Two ---") {
+                part = 1;
+            }
+
+            if let Some(value) = emphasised_values(line).pop() {
+                let example = examples.inputs.len().saturating_sub(1);
+                pending_answer = examples.inputs.is_empty()
+                    || (introduces_example(line) && line.trim_end().ends_with(':'));
+                examples.answers[part] = Some(Answer { value, example });
+            }
+
+            if !line.trim().is_empty() {
+                previous_line = line;
+            }
+        }
+
+        examples
+    }
+
+    /// Add an input unless it was already shown, returning its index.
+    fn push_input(&mut self, lines: &[&str]) -> usize {
+        let end = lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        let input = lines[..end].join("\n") + "\n";
+
+        if let Some(index) = self.inputs.iter().position(|i| *i == input) {
+            return index;
+        }
+        self.inputs.push(input);
+        self.inputs.len() - 1
+    }
+
+    /// The name of the file of an example: `NN.txt` if the puzzle has a single
+    /// example, `NN-1.txt`, `NN-2.txt`, ... otherwise.
+    #[must_use]
+    pub fn file_name(&self, day: Day, index: usize) -> String {
+        if self.inputs.len() > 1 {
+            format!("{day}-{}.txt", index + 1)
+        } else {
+            format!("{day}.txt")
+        }
+    }
+
+    /// Write the example inputs to `data/<year>/examples/`, skipping files that
+    /// already have contents. Returns the paths that were written.
+    pub fn write(&self, puzzle: PuzzleId) -> Result<Vec<String>, io::Error> {
+        let folder = format!("{}/examples", config::get().year_dir(puzzle.year));
+        fs::create_dir_all(&folder)?;
+
+        let mut written = vec![];
+        for (index, input) in self.inputs.iter().enumerate() {
+            let path = format!("{folder}/{}", self.file_name(puzzle.day, index));
+            if fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty()) {
+                continue;
+            }
+            fs::write(&path, input)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+
+    /// Read the description of a puzzle from `data/<year>/puzzles/`.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, io::Error> {
+        fs::read_to_string(get_puzzle_path(puzzle)).map(|markdown| Self::parse(&markdown))
+    }
+}
+
+/// Whether a line introduces an example, rather than an illustration of how an
+/// example is solved (e.g. "In the above example, this is the path:").
+fn introduces_example(line: &str) -> bool {
+    const INTRODUCTIONS: [&str; 4] = [
+        "for example",
+        "an example",
+        "another example",
+        "interesting example",
+    ];
+
+    let line = line.to_lowercase();
+    INTRODUCTIONS.iter().any(|s| line.contains(s))
+}
+
+/// The values of emphasised inline code on a line, which `aoc-cli` writes as
+/// `` `*142*` `` and `cargo download` as ``*`142`*``.
+fn emphasised_values(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['`', '*']) {
+        let (open, close) = if rest[start..].starts_with("`*") {
+            ("`*", "*`")
+        } else if rest[start..].starts_with("*`") {
+            ("*`", "`*")
+        } else {
+            rest = &rest[start + 1..];
+            continue;
+        };
+
+        let content = &rest[start + open.len()..];
+        match content.find(close).map(|end| &content[..end]) {
+            Some(value) if is_value(value) => {
+                values.push(value.to_string());
+                rest = &content[value.len() + close.len()..];
+            }
+            _ => rest = &rest[start + 1..],
+        }
+    }
+
+    values
+}
+
+fn is_value(s: &str) -> bool {
+    !s.is_empty() && !s.contains('`') && s.trim() == s
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_values, Answer, Examples};
+    use crate::day;
+
+    const DESCRIPTION: &str = "\
+\\--- Day 1: Trebuchet?! ---
+----------
+
+The newly-improved calibration document consists of lines of text. For example:
+
+```
+1abc2
+pqr3stu8vwx
+
+```
+
+In this example, the values are `12` and `38`. Adding these together produces `*50*`.
+
+\\--- Part Two ---
+----------
+
+Some of the digits are spelled out. For example:
+
+```
+two1nine
+eightwothree
+```
+
+What is the sum? Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn finds_emphasised_values() {
+        assert_eq!(
+            emphasised_values("values `12`, `*38*` and *`CCC`*, *not* `this`"),
+            vec!["38".to_string(), "CCC".to_string()]
+        );
+        assert!(emphasised_values("`*` or `**` *emphasis*").is_empty());
+    }
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let examples = Examples::parse(DESCRIPTION);
+        assert_eq!(
+            examples.inputs,
+            vec!["1abc2\npqr3stu8vwx\n", "two1nine\neightwothree\n"]
+        );
+        assert_eq!(
+            examples.answers,
+            [
+                Some(Answer {
+                    value: "50".into(),
+                    example: 0
+                }),
+                Some(Answer {
+                    value: "281".into(),
+                    example: 1
+                })
+            ]
+        );
+        assert_eq!(examples.file_name(day!(1), 1), "01-2.txt");
+        assert_eq!(Examples::default().file_name(day!(1), 0), "01.txt");
+    }
+
+    #[test]
+    fn attaches_answers_to_their_example() {
+        let description = "For example:\n\n```\nAAA = (AAA, AAA)\n```\n\n\
+                           Here is a situation that takes `*6*` steps, for example:\n\n\
+                           ```\nAAA = (BBB, BBB)\n```\n\nAn illustration:\n\n```\n.#.\n```\n\n\
+                           \\--- Part Two ---\n\nThe same example takes `*4*` steps.\n";
+        let examples = Examples::parse(description);
+        assert_eq!(
+            examples.inputs,
+            vec!["AAA = (AAA, AAA)\n", "AAA = (BBB, BBB)\n"]
+        );
+        assert_eq!(examples.answers[0].as_ref().map(|a| a.example), Some(1));
+        assert_eq!(
+            examples.answers[1].as_ref().map(|a| a.value.as_str()),
+            Some("4")
+        );
+        assert_eq!(examples.answers[1].as_ref().map(|a| a.example), Some(1));
+    }
+}
@@ -0,0 +1,263 @@
+/// Converts the puzzle pages of the Advent of Code website to markdown, like
+/// `aoc-cli` does when it saves a puzzle description.
+///
+/// This only understands the handful of tags used on those pages: headings,
+/// paragraphs, lists, code blocks, inline code, emphasis and links.
+use std::fmt::Write;
+
+/// The contents of all `<article>` elements of a page, which hold the puzzle
+/// description or the response to a submitted answer.
+#[must_use]
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find("</article>")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+
+        articles.push(&rest[content_start..end]);
+        rest = &rest[end + "</article>".len()..];
+    }
+
+    articles
+}
+
+/// Convert the articles of a puzzle page to markdown.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    let markdown: Vec<String> = articles(html).into_iter().map(convert).collect();
+    markdown.join("\n\n") + "\n"
+}
+
+/// Strip all tags from a fragment of html, keeping only its text.
+#[must_use]
+pub fn to_text(html: &str) -> String {
+    let mut text = String::new();
+
+    for token in tokenize(html) {
+        if let Token::Text(s) = token {
+            text.push_str(&decode_entities(s));
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+enum Token<'a> {
+    /// An opening tag with its name and attributes, e.g. `a href="/2023"`.
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+
+        let tag = rest[start + 1..end].trim_end_matches('/').trim();
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim()));
+        } else {
+            let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+            tokens.push(Token::Open(name, attributes));
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    tokens
+}
+
+fn convert(article: &str) -> String {
+    let mut markdown = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+
+    for token in tokenize(article) {
+        match token {
+            Token::Open(name, attributes) => match name {
+                "h2" => markdown.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                "code" if !in_pre => markdown.push('`'),
+                "em" => markdown.push('*'),
+                "li" => markdown.push_str("- "),
+                "a" => {
+                    markdown.push('[');
+                    links.push(attribute(attributes, "href").unwrap_or_default());
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name {
+                "h2" | "p" => markdown.push_str("\n\n"),
+                "pre" => {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+                "code" if !in_pre => markdown.push('`'),
+                "em" => markdown.push('*'),
+                "li" => markdown.push('\n'),
+                "ul" => markdown.push('\n'),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    let _ = write!(markdown, "]({href})");
+                }
+                _ => {}
+            },
+            // skip the line breaks between block elements.
+            Token::Text(text) if !in_pre && text.trim().is_empty() && text.contains('\n') => {}
+            Token::Text(text) => markdown.push_str(&decode_entities(text)),
+        }
+    }
+
+    // collapse the blank lines left by nested blocks.
+    let mut lines: Vec<&str> = vec![];
+    for line in markdown.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n").trim_end().to_string()
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attributes[start..].find('"')? + start;
+    Some(decode_entities(&attributes[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => {
+                    let code = match name.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => name.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match replacement {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, to_markdown, to_text};
+
+    const PAGE: &str = concat!(
+        "<html><body><main>\n",
+        "<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>",
+        "<p>Something is <em>wrong</em> with <a href=\"/2023/events\">global snow production</a>.</p>\n",
+        "<p>For example:</p>\n",
+        "<pre><code>1abc2\npqr3stu8vwx\n</code></pre>\n",
+        "<ul>\n<li>The value is <code>12</code>.</li>\n<li>It is <code>a &lt; b &amp;&amp; c</code>.</li>\n</ul>\n",
+        "</article>\n",
+        "<p>Your puzzle answer was <code>142</code>.</p>\n",
+        "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>It&#39;s <em class=\"star\">more</em>.</p></article>\n",
+        "</main></body></html>"
+    );
+
+    #[test]
+    fn finds_articles() {
+        let found = articles(PAGE);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].starts_with("<h2>--- Day 1"));
+        assert!(found[1].ends_with("</p>"));
+    }
+
+    #[test]
+    fn converts_to_markdown() {
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Something is *wrong* with [global snow production](/2023/events).",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "- The value is `12`.",
+            "- It is `a < b && c`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "It's *more*.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(to_markdown(PAGE), expected);
+    }
+
+    #[test]
+    fn strips_tags() {
+        let html = "<p>That's not the right answer; your answer is too <em>high</em>. \
+                    <a href=\"/2023/day/1\">[Return to Day 1]</a></p>";
+        assert_eq!(
+            to_text(html),
+            "That's not the right answer; your answer is too high. [Return to Day 1]"
+        );
+    }
+}
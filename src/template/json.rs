@@ -0,0 +1,279 @@
+/// Minimal reader and writer for flat JSON objects, i.e. objects whose values
+/// are strings, numbers, booleans or `null`. This is all the runner protocol
+/// needs, so we avoid pulling in a serialization framework.
+use std::{collections::HashMap, fmt::Display, iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Numbers are kept in their textual representation, so that integers
+    /// of any size survive a round trip.
+    Number(String),
+    String(String),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<Option<&str>> for Value {
+    fn from(s: Option<&str>) -> Self {
+        s.map_or(Value::Null, Value::from)
+    }
+}
+
+impl From<u128> for Value {
+    fn from(n: u128) -> Self {
+        Value::Number(n.to_string())
+    }
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self {
+        Value::Number(n.to_string())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Number(n) => write!(f, "{n}"),
+            Value::String(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "\"")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError(String);
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid JSON: {}", self.0)
+    }
+}
+
+/// Serialize the fields as a single-line JSON object, keeping their order.
+#[must_use]
+pub fn write_object(fields: &[(&str, Value)]) -> String {
+    let members: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", Value::from(*key), value))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// Parse a flat JSON object. Nested arrays and objects are not supported.
+pub fn parse_object(s: &str) -> Result<HashMap<String, Value>, ParseError> {
+    let mut chars = s.trim().chars().peekable();
+    let mut object = HashMap::new();
+
+    expect(&mut chars, '{')?;
+    skip_whitespace(&mut chars);
+
+    if chars.peek() == Some(&'}') {
+        chars.next();
+    } else {
+        loop {
+            skip_whitespace(&mut chars);
+            let key = parse_string(&mut chars)?;
+            skip_whitespace(&mut chars);
+            expect(&mut chars, ':')?;
+            skip_whitespace(&mut chars);
+            let value = parse_value(&mut chars)?;
+            object.insert(key, value);
+            skip_whitespace(&mut chars);
+
+            match chars.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                c => return Err(ParseError(format!("expected ',' or '}}', found {c:?}"))),
+            }
+        }
+    }
+
+    if chars.next().is_some() {
+        return Err(ParseError("trailing characters after object".into()));
+    }
+
+    Ok(object)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), ParseError> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        c => Err(ParseError(format!("expected {expected:?}, found {c:?}"))),
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Value, ParseError> {
+    match chars.peek() {
+        Some('"') => Ok(Value::String(parse_string(chars)?)),
+        Some('-' | '0'..='9') => {
+            let mut number = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+                    break;
+                }
+                number.push(c);
+                chars.next();
+            }
+            Ok(Value::Number(number))
+        }
+        Some(_) => {
+            let mut word = String::new();
+            while chars.peek().is_some_and(char::is_ascii_alphabetic) {
+                word.push(chars.next().unwrap());
+            }
+            match word.as_str() {
+                "null" => Ok(Value::Null),
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                _ => Err(ParseError(format!("unsupported value {word:?}"))),
+            }
+        }
+        None => Err(ParseError("unexpected end of input".into())),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, ParseError> {
+    expect(chars, '"')?;
+    let mut s = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('"') => s.push('"'),
+                Some('\\') => s.push('\\'),
+                Some('/') => s.push('/'),
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('b') => s.push('\u{8}'),
+                Some('f') => s.push('\u{c}'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| ParseError(format!("invalid unicode escape {hex:?}")))?;
+                    s.push(c);
+                }
+                c => return Err(ParseError(format!("invalid escape {c:?}"))),
+            },
+            Some(c) => s.push(c),
+            None => return Err(ParseError("unterminated string".into())),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_object, write_object, Value};
+
+    #[test]
+    fn round_trips_values() {
+        let line = write_object(&[
+            ("day", Value::from(1u8)),
+            ("answer", Value::from("a \"quoted\"\nmulti-line\\answer")),
+            ("missing", Value::Null),
+            ("flag", Value::from(true)),
+        ]);
+        assert!(!line.contains('\n'));
+
+        let object = parse_object(&line).unwrap();
+        assert_eq!(object["day"].as_u64(), Some(1));
+        assert_eq!(
+            object["answer"].as_str(),
+            Some("a \"quoted\"\nmulti-line\\answer")
+        );
+        assert_eq!(object["missing"], Value::Null);
+        assert_eq!(object["flag"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn parses_whitespace_and_escapes() {
+        let object = parse_object(r#" { "a" : "µs" , "b": -1.5e3 } "#).unwrap();
+        assert_eq!(object["a"].as_str(), Some("µs"));
+        assert_eq!(object["b"].as_f64(), Some(-1500.0));
+        assert!(parse_object("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(parse_object("Part 1: 42 (1.0ms)").is_err());
+        assert!(parse_object(r#"{"a": "unterminated}"#).is_err());
+        assert!(parse_object(r#"{"a": 1} trailing"#).is_err());
+        assert!(parse_object(r#"{"a": [1]}"#).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+/// Module that records every submitted answer, so that answers which are known
+/// to be wrong are not submitted again.
+///
+/// Every year has its own ledger in `data/YYYY/submissions.jsonl`, which holds one JSON object per
+/// submission, e.g. `{"day":1,"part":2,"answer":"281","outcome":"too_low","timestamp":1701400000,"wait_until":1701400060}`.
+/// `wait_until` is set when the website asked to wait before the next attempt.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+
+use crate::template::{
+    aoc_client::Submission,
+    config,
+    json::{self, Value},
+};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(line) => write!(f, "could not parse submission \"{line}\""),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// Not checked, because the previous answer was submitted too recently.
+    TooSoon,
+    /// Not checked, because the part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooSoon => "too_soon",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the answer was checked and found to be wrong.
+    pub fn is_rejected(self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "too_soon" => Ok(Outcome::TooSoon),
+            "wrong_level" => Ok(Outcome::WrongLevel),
+            "unknown" => Ok(Outcome::Unknown),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// When the website accepts the next answer, in seconds since the unix epoch.
+    pub wait_until: Option<u64>,
+}
+
+impl Entry {
+    /// Record the response of the website to an answer submitted at `timestamp`.
+    #[must_use]
+    pub fn from_submission(
+        day: Day,
+        part: u8,
+        answer: &str,
+        submission: &Submission,
+        timestamp: u64,
+    ) -> Self {
+        let message = submission.message();
+
+        let outcome = match submission {
+            Submission::Correct(_) => Outcome::Correct,
+            Submission::Incorrect(_) if message.contains("too high") => Outcome::TooHigh,
+            Submission::Incorrect(_) if message.contains("too low") => Outcome::TooLow,
+            Submission::Incorrect(_) => Outcome::Incorrect,
+            Submission::TooSoon(_) => Outcome::TooSoon,
+            Submission::WrongLevel(_) => Outcome::WrongLevel,
+            Submission::Unknown(_) => Outcome::Unknown,
+        };
+
+        Entry {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp,
+            wait_until: parse_wait(message).map(|wait| timestamp + wait.as_secs()),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        json::write_object(&[
+            ("day", Value::from(self.day.into_inner())),
+            ("part", Value::from(self.part)),
+            ("answer", Value::from(self.answer.as_str())),
+            ("outcome", Value::from(self.outcome.as_str())),
+            ("timestamp", Value::from(u128::from(self.timestamp))),
+            (
+                "wait_until",
+                self.wait_until
+                    .map_or(Value::Null, |t| Value::from(u128::from(t))),
+            ),
+        ])
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let object = json::parse_object(line).ok()?;
+
+        Some(Entry {
+            day: Day::new(u8::try_from(object.get("day")?.as_u64()?).ok()?)?,
+            part: u8::try_from(object.get("part")?.as_u64()?).ok()?,
+            answer: object.get("answer")?.as_str()?.to_string(),
+            outcome: object.get("outcome")?.as_str()?.parse().ok()?,
+            timestamp: object.get("timestamp")?.as_u64()?,
+            wait_until: match object.get("wait_until") {
+                None | Some(Value::Null) => None,
+                Some(value) => Some(value.as_u64()?),
+            },
+        })
+    }
+}
+
+/// Parse how long the website asks to wait before the next attempt, e.g. from
+/// "You have 1m 12s left to wait." or "Please wait one minute before trying again."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    if let Some(captures) = left.captures(message) {
+        let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        let seconds: u64 = captures[2].parse().ok()?;
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    let minutes = Regex::new(r"(?i)wait (\w+) minutes? before trying again").unwrap();
+    let count = minutes.captures(message)?;
+    let count = match &count[1] {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    Some(Duration::from_secs(count * 60))
+}
+
+/// Why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    Cooldown(Duration),
+    AlreadySolved(String),
+    AlreadyRejected(Outcome),
+    /// The answer is not below an answer that was too high.
+    TooHigh(String),
+    /// The answer is not above an answer that was too low.
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Cooldown(wait) => write!(f, "please wait {wait:?} before trying again"),
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with {answer}")
+            }
+            Refusal::AlreadyRejected(outcome) => match outcome {
+                Outcome::TooHigh => write!(f, "this answer was already rejected as too high"),
+                Outcome::TooLow => write!(f, "this answer was already rejected as too low"),
+                _ => write!(f, "this answer was already rejected"),
+            },
+            Refusal::TooHigh(bound) => write!(f, "{bound} was already too high"),
+            Refusal::TooLow(bound) => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: String,
+    entries: Vec<Entry>,
+}
+
+#[must_use]
+pub fn get_path(year: Year) -> String {
+    format!("{}/submissions.jsonl", config::get().year_dir(year))
+}
+
+impl Ledger {
+    /// Read the ledger of a year, which is empty if nothing was submitted yet.
+    pub fn load(year: Year) -> Result<Self, Error> {
+        let path = get_path(year);
+        let ledger = match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ledger::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Ledger { path, ..ledger })
+    }
+
+    fn parse(contents: &str) -> Result<Self, Error> {
+        let entries = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Entry::from_json(line).ok_or_else(|| Error::Parser(line.into())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Ledger {
+            entries,
+            ..Ledger::default()
+        })
+    }
+
+    /// The submissions for a part, oldest first.
+    pub fn entries(&self, day: Day, part: u8) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.day == day && e.part == part)
+    }
+
+    /// Add a submission to the ledger and append it to the file.
+    pub fn record(&mut self, entry: Entry) -> Result<(), Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", entry.to_json())?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Decide whether an answer should be submitted at `now`, based on the
+    /// earlier submissions for the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let entries: Vec<&Entry> = self.entries(day, part).collect();
+
+        if let Some(wait_until) = entries.iter().filter_map(|e| e.wait_until).max() {
+            if wait_until > now {
+                return Err(Refusal::Cooldown(Duration::from_secs(wait_until - now)));
+            }
+        }
+
+        if let Some(solved) = entries.iter().find(|e| e.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        if let Some(rejected) = entries
+            .iter()
+            .find(|e| e.answer == answer && e.outcome.is_rejected())
+        {
+            return Err(Refusal::AlreadyRejected(rejected.outcome));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |outcome: Outcome| {
+            entries
+                .iter()
+                .filter(move |e| e.outcome == outcome)
+                .filter_map(|e| Some((e.answer.parse::<i128>().ok()?, &e.answer)))
+        };
+
+        if let Some((_, high)) = bound(Outcome::TooHigh).filter(|(h, _)| value >= *h).min() {
+            return Err(Refusal::TooHigh(high.clone()));
+        }
+
+        if let Some((_, low)) = bound(Outcome::TooLow).filter(|(l, _)| value <= *l).max() {
+            return Err(Refusal::TooLow(low.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// The current time in seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_wait, Entry, Ledger, Outcome, Refusal};
+    use crate::day;
+    use crate::template::aoc_client::Submission;
+
+    fn entry(answer: &str, submission: Submission, timestamp: u64) -> Entry {
+        Entry::from_submission(day!(1), 2, answer, &submission, timestamp)
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        let too_high = entry(
+            "300",
+            Submission::Incorrect(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again.".into(),
+            ),
+            1000,
+        );
+        assert_eq!(too_high.outcome, Outcome::TooHigh);
+        assert_eq!(too_high.wait_until, Some(1060));
+
+        let too_soon = entry(
+            "281",
+            Submission::TooSoon(
+                "You gave an answer too recently. You have 1m 12s left to wait.".into(),
+            ),
+            1000,
+        );
+        assert_eq!(too_soon.outcome, Outcome::TooSoon);
+        assert_eq!(too_soon.wait_until, Some(1072));
+
+        assert_eq!(
+            parse_wait("You have 42s left to wait."),
+            Some(Duration::from_secs(42))
+        );
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let entry = entry(
+            "281",
+            Submission::Correct("That's the right answer!".into()),
+            1000,
+        );
+        let line = entry.to_json();
+        assert_eq!(
+            line,
+            r#"{"day":1,"part":2,"answer":"281","outcome":"correct","timestamp":1000,"wait_until":null}"#
+        );
+        assert_eq!(Entry::from_json(&line), Some(entry));
+        assert!(Ledger::parse("not a submission").is_err());
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = Ledger {
+            entries: vec![
+                entry(
+                    "300",
+                    Submission::Incorrect(
+                        "That's not the right answer; your answer is too high.".into(),
+                    ),
+                    1000,
+                ),
+                entry(
+                    "200",
+                    Submission::Incorrect(
+                        "That's not the right answer; your answer is too low.".into(),
+                    ),
+                    1100,
+                ),
+                entry(
+                    "abc",
+                    Submission::Incorrect("That's not the right answer.".into()),
+                    1200,
+                ),
+            ],
+            ..Ledger::default()
+        };
+
+        assert_eq!(ledger.check(day!(1), 2, "281", 2000), Ok(()));
+        assert_eq!(ledger.check(day!(1), 1, "300", 2000), Ok(()));
+        assert_eq!(
+            ledger.check(day!(1), 2, "300", 2000),
+            Err(Refusal::AlreadyRejected(Outcome::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 2, "abc", 2000),
+            Err(Refusal::AlreadyRejected(Outcome::Incorrect))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 2, "301", 2000),
+            Err(Refusal::TooHigh("300".into()))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 2, "-5", 2000),
+            Err(Refusal::TooLow("200".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_during_cooldown_and_after_solving() {
+        let mut ledger = Ledger {
+            entries: vec![entry(
+                "1",
+                Submission::TooSoon(
+                    "You gave an answer too recently. You have 30s left to wait.".into(),
+                ),
+                1000,
+            )],
+            ..Ledger::default()
+        };
+
+        assert_eq!(
+            ledger.check(day!(1), 2, "281", 1010),
+            Err(Refusal::Cooldown(Duration::from_secs(20)))
+        );
+        assert_eq!(ledger.check(day!(1), 2, "281", 1030), Ok(()));
+
+        ledger.entries.push(entry(
+            "281",
+            Submission::Correct("That's the right answer!".into()),
+            1040,
+        ));
+        assert_eq!(
+            ledger.check(day!(1), 2, "282", 1050),
+            Err(Refusal::AlreadySolved("281".into()))
+        );
+    }
+}
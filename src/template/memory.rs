@@ -0,0 +1,206 @@
+/// A global allocator that counts the allocations of a part, for `--mem`.
+///
+/// The allocator is only installed with the `mem` feature, which `--mem` builds
+/// the solutions with. Counting is switched off outside of [`measure`], so apart
+/// from a thread-local lookup per allocation, even those solutions are not
+/// affected when they run without `--mem`.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+#[cfg(feature = "mem")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The counters of the current thread. Only the thread that runs a part counts
+/// its allocations, so that parts can be measured while other threads allocate.
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    enabled: bool,
+    allocations: u64,
+    allocated_bytes: u64,
+    // live bytes can become negative when memory from before the measurement is freed.
+    live_bytes: i64,
+    peak_bytes: i64,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so accessing it never allocates.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            enabled: false,
+            allocations: 0,
+            allocated_bytes: 0,
+            live_bytes: 0,
+            peak_bytes: 0,
+        })
+    };
+}
+
+/// The allocator that counts, which forwards all allocations to [`System`].
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        update(|c| c.live_bytes -= layout.size() as i64);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Update the counters of the current thread, if it is being measured.
+fn update(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        if counters.enabled {
+            f(&mut counters);
+            cell.set(counters);
+        }
+    });
+}
+
+/// Count an allocation of `size` bytes, which replaces `freed` bytes in case of
+/// a reallocation.
+fn record(size: usize, freed: usize) {
+    update(|c| {
+        c.allocations += 1;
+        c.allocated_bytes += size as u64;
+        c.live_bytes += size as i64 - freed as i64;
+        c.peak_bytes = c.peak_bytes.max(c.live_bytes);
+    });
+}
+
+/// The allocations made while running a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The number of allocations, including reallocations.
+    pub allocations: u64,
+    /// The total number of bytes allocated.
+    pub allocated_bytes: u64,
+    /// The largest number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs · {} total · {} peak",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Run a function and count the allocations it makes on the current thread.
+/// Measurements can not be nested.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    COUNTERS.with(|cell| {
+        cell.set(Counters {
+            enabled: true,
+            ..Counters::default()
+        });
+    });
+
+    let result = func();
+
+    let counters = COUNTERS.with(|cell| {
+        let counters = cell.get();
+        cell.set(Counters::default());
+        counters
+    });
+
+    let stats = AllocStats {
+        allocations: counters.allocations,
+        allocated_bytes: counters.allocated_bytes,
+        peak_bytes: u64::try_from(counters.peak_bytes).unwrap_or(0),
+    };
+
+    (result, stats)
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure};
+
+    #[test]
+    #[cfg(feature = "mem")]
+    fn counts_allocations() {
+        let (sum, stats) = measure(|| {
+            let small = vec![0_u8; 1000];
+            let large = vec![0_u8; 4000];
+            small.len() + large.len()
+        });
+        assert_eq!(sum, 5000);
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.allocated_bytes, 5000);
+        assert_eq!(stats.peak_bytes, 5000);
+    }
+
+    #[test]
+    #[cfg(feature = "mem")]
+    fn tracks_peak_of_live_memory() {
+        let (_, stats) = measure(|| {
+            for _ in 0..10 {
+                drop(vec![0_u8; 10_000]);
+            }
+        });
+        assert_eq!(stats.allocations, 10);
+        assert_eq!(stats.allocated_bytes, 100_000);
+        assert_eq!(stats.peak_bytes, 10_000);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+}
@@ -1,48 +1,399 @@
-use crate::Day;
-use std::{env, fs};
+use crate::PuzzleId;
+use std::{
+    fmt::Display,
+    fs, io,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod baselines;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod html;
+pub mod json;
+pub mod ledger;
+pub mod memory;
+pub mod params;
+pub mod progress;
 pub mod readme_benchmarks;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod stats;
+pub mod trace;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// An input or example file that could not be read.
+#[derive(Debug)]
+pub struct ReadError {
+    pub path: PathBuf,
+    pub source: io::Error,
+    hint: Option<String>,
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+impl ReadError {
+    fn new(path: &Path, source: io::Error) -> Self {
+        ReadError {
+            path: path.to_path_buf(),
+            source,
+            hint: None,
+        }
+    }
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read \"{}\": {}",
+            self.path.display(),
+            self.source
+        )?;
+        if let Some(hint) = &self.hint {
+            write!(f, ". {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn read_data_file(folder: &str, puzzle: PuzzleId, name: &str) -> Result<String, ReadError> {
+    let path = Path::new(&config::get().year_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        .join(name);
+
+    fs::read_to_string(&path).map_err(|e| {
+        let mut error = ReadError::new(&path, e);
+        if error.source.kind() == io::ErrorKind::NotFound {
+            error.hint = match folder {
+                "inputs" => Some(format!(
+                    "Download it with `cargo download {} --year {}`",
+                    puzzle.day, puzzle.year
+                )),
+                "examples" => Some("Paste the example of the puzzle into it".into()),
+                _ => None,
+            };
+        }
+        error
+    })
+}
+
+/// Helper function that reads a text file of a puzzle to a string. E.g. like
+/// `data/2023/inputs/01.txt`.
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> Result<String, ReadError> {
+    read_data_file(folder, puzzle, &format!("{}.txt", puzzle.day))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> Result<String, ReadError> {
+    read_data_file(folder, puzzle, &format!("{}-{part}.txt", puzzle.day))
+}
+
+/// Helper function that reads an input from a path, or from stdin if the path is `-`.
+pub fn read_input_from(path: &str) -> Result<String, ReadError> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| ReadError::new(Path::new("<stdin>"), e))?;
+        return Ok(input);
+    }
+
+    fs::read_to_string(path).map_err(|e| ReadError::new(Path::new(path), e))
+}
+
+/// Helper function that parses a duration such as `500ms` or `2.5s`. A number
+/// without a unit is taken to be in seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration \"{s}\""))?;
+    let secs = match unit.trim() {
+        "ns" => value / 1e9,
+        "µs" | "us" => value / 1e6,
+        "ms" => value / 1e3,
+        "" | "s" => value,
+        "m" | "min" => value * 60.0,
+        unit => return Err(format!("invalid duration unit \"{unit}\"")),
+    };
+
+    Duration::try_from_secs_f64(secs).map_err(|e| e.to_string())
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE`, registers the puzzle as a
+/// [`solution::Solution`] and sets up the input and runner for each part.
+///
+/// Pass a parse function as the third argument, e.g. `solution!(2023, 17, parse)`,
+/// to parse the input once and share the result between both parts. The parts
+/// then take a reference to the parsed input, and the runner times the parse
+/// step separately.
+///
+/// Named parameters with defaults can be declared last, e.g.
+/// `solution!(2023, 21, params { steps: u64 = 64 })`. This generates a `Params`
+/// struct that both parts take as their second argument, and whose fields can
+/// be overridden in the config or with `--param steps=5000`. See [`params`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@common $year:expr, $day:expr) => {
+        /// The year of the current event.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
+
+        /// The registry entry of the current day.
+        pub struct DaySolution;
+
+        // Unused when the day is compiled into the library.
+        #[allow(dead_code)]
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            use advent_of_code::template::solution::Solution;
+            use advent_of_code::template::runner;
+            let input = match runner::read_input(PUZZLE) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            };
+            if input.is_custom {
+                runner::mark_custom_run("of another input than the puzzle input");
+            }
+            runner::start_timeout(PUZZLE);
+            let reports = DaySolution.run(&input.text);
+
+            if reports
+                .iter()
+                .any(|report| report.is_regression() || report.is_failure())
+            {
+                std::process::exit(1);
+            }
+        }
+    };
+    (@params $($name:ident: $ty:ty = $default:expr),* $(,)?) => {
+        /// The parameters of both parts, see `--param`.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Params {
+            $(pub $name: $ty,)*
+        }
+
+        impl Default for Params {
+            fn default() -> Self {
+                Params {
+                    $($name: $default,)*
+                }
+            }
+        }
+
+        impl Params {
+            /// The names of all parameters.
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($name)),*];
+
+            /// The defaults, with the given values overridden.
+            pub fn from_overrides(
+                overrides: &[(String, String)],
+            ) -> Result<Self, advent_of_code::template::params::Error> {
+                use advent_of_code::template::params;
+                let mut result = Self::default();
+                for (name, value) in overrides {
+                    match name.as_str() {
+                        $(stringify!($name) => params::set(&mut result.$name, name, value)?,)*
+                        _ => return Err(params::unknown(name, Self::NAMES)),
+                    }
+                }
+                Ok(result)
+            }
+
+            /// The defaults, with the configured values and the values passed
+            /// as `--param name=value`. Values that differ from the defaults
+            /// change the answers, so they can not be submitted or checked.
+            pub fn from_args() -> Self {
+                let overrides = advent_of_code::template::params::from_args(PUZZLE, Self::NAMES);
+                let params = Self::from_overrides(&overrides).unwrap_or_else(|e| {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                });
+                if params != Self::default() {
+                    advent_of_code::template::runner::mark_custom_run(
+                        "with parameters that differ from their defaults",
+                    );
+                }
+                params
+            }
+        }
+    };
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@common $year, $day);
+
+        impl advent_of_code::template::solution::Solution for DaySolution {
+            fn puzzle(&self) -> advent_of_code::PuzzleId {
+                PUZZLE
+            }
+
+            fn part_one(&self, input: &str) -> Option<String> {
+                part_one(input).map(|result| result.to_string())
+            }
+
+            fn part_two(&self, input: &str) -> Option<String> {
+                part_two(input).map(|result| result.to_string())
+            }
+
+            fn run(&self, input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                // refuse `--param`, which would be ignored otherwise.
+                let _ = advent_of_code::template::params::from_args(PUZZLE, &[]);
+                vec![
+                    run_part(part_one, input, PUZZLE, 1),
+                    run_part(part_two, input, PUZZLE, 2),
+                ]
+            }
         }
     };
+    ($year:expr, $day:expr, params { $($params:tt)* }) => {
+        $crate::solution!(@common $year, $day);
+        $crate::solution!(@params $($params)*);
+
+        impl advent_of_code::template::solution::Solution for DaySolution {
+            fn puzzle(&self) -> advent_of_code::PuzzleId {
+                PUZZLE
+            }
+
+            fn part_one(&self, input: &str) -> Option<String> {
+                part_one(input, &Params::default()).map(|result| result.to_string())
+            }
+
+            fn part_two(&self, input: &str) -> Option<String> {
+                part_two(input, &Params::default()).map(|result| result.to_string())
+            }
+
+            fn run(&self, input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                let params = Params::from_args();
+                vec![
+                    run_part(|input| part_one(input, &params), input, PUZZLE, 1),
+                    run_part(|input| part_two(input, &params), input, PUZZLE, 2),
+                ]
+            }
+        }
+    };
+    ($year:expr, $day:expr, $parse:ident) => {
+        $crate::solution!(@common $year, $day);
+
+        impl advent_of_code::template::solution::Solution for DaySolution {
+            fn puzzle(&self) -> advent_of_code::PuzzleId {
+                PUZZLE
+            }
+
+            fn part_one(&self, input: &str) -> Option<String> {
+                part_one(&$parse(input)).map(|result| result.to_string())
+            }
+
+            fn part_two(&self, input: &str) -> Option<String> {
+                part_two(&$parse(input)).map(|result| result.to_string())
+            }
+
+            fn run(&self, input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                let _ = advent_of_code::template::params::from_args(PUZZLE, &[]);
+                let (parsed, parse_report) = run_parse($parse, input, PUZZLE);
+                let Some(parsed) = parsed else {
+                    return vec![parse_report];
+                };
+                vec![
+                    parse_report,
+                    run_part(part_one, &parsed, PUZZLE, 1),
+                    run_part(part_two, &parsed, PUZZLE, 2),
+                ]
+            }
+        }
+    };
+    ($year:expr, $day:expr, $parse:ident, params { $($params:tt)* }) => {
+        $crate::solution!(@common $year, $day);
+        $crate::solution!(@params $($params)*);
+
+        impl advent_of_code::template::solution::Solution for DaySolution {
+            fn puzzle(&self) -> advent_of_code::PuzzleId {
+                PUZZLE
+            }
+
+            fn part_one(&self, input: &str) -> Option<String> {
+                part_one(&$parse(input), &Params::default()).map(|result| result.to_string())
+            }
+
+            fn part_two(&self, input: &str) -> Option<String> {
+                part_two(&$parse(input), &Params::default()).map(|result| result.to_string())
+            }
+
+            fn run(&self, input: &str) -> Vec<advent_of_code::template::report::PartReport> {
+                use advent_of_code::template::runner::*;
+                let params = Params::from_args();
+                let (parsed, parse_report) = run_parse($parse, input, PUZZLE);
+                let Some(parsed) = parsed else {
+                    return vec![parse_report];
+                };
+                vec![
+                    parse_report,
+                    run_part(|parsed| part_one(parsed, &params), &parsed, PUZZLE, 1),
+                    run_part(|parsed| part_two(parsed, &params), &parsed, PUZZLE, 2),
+                ]
+            }
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_duration, read_file};
+    use crate::{day, year, PuzzleId};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("2.5s"), Ok(Duration::from_millis(2500)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10µs"), Ok(Duration::from_micros(10)));
+        assert_eq!(parse_duration("10us"), Ok(Duration::from_micros(10)));
+        assert_eq!(parse_duration("1500ns"), Ok(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("1min"), Ok(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn explains_missing_files() {
+        let error = read_file("inputs", PuzzleId::new(year!(2015), day!(25))).unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("could not read \"data/2015/inputs/25.txt\""));
+        assert!(message.ends_with("Download it with `cargo download 25 --year 2015`"));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("5 parsecs").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
 }
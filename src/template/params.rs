@@ -0,0 +1,195 @@
+/// Module that reads the parameters of a solution from the config and the
+/// command-line.
+///
+/// Parameters are declared in the `solution!` macro, e.g.
+/// `solution!(2023, 21, params { steps: u64 = 64 })`, and can be overridden
+/// in the `[params.2023-21]` table of the config or with
+/// `cargo solve 21 --param steps=5000`.
+use std::{env, fmt::Display, process, str::FromStr};
+
+use crate::template::config::{self, Config};
+use crate::PuzzleId;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Syntax(String),
+    Unknown { name: String, known: Vec<String> },
+    Value { name: String, value: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Syntax(arg) => {
+                write!(f, "expecting a parameter like `steps=64`, got \"{arg}\"")
+            }
+            Error::Unknown { name, known } if known.is_empty() => {
+                write!(
+                    f,
+                    "unknown parameter \"{name}\", this solution has no parameters"
+                )
+            }
+            Error::Unknown { name, known } => write!(
+                f,
+                "unknown parameter \"{name}\", expecting one of: {}",
+                known.join(", ")
+            ),
+            Error::Value { name, value } => {
+                write!(f, "invalid value \"{value}\" for parameter \"{name}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The error for a parameter that is not one of the `known` parameters.
+#[must_use]
+pub fn unknown(name: &str, known: &[&str]) -> Error {
+    Error::Unknown {
+        name: name.into(),
+        known: known.iter().map(ToString::to_string).collect(),
+    }
+}
+
+/// Collect the `--param name=value` overrides from a list of arguments.
+pub fn parse_overrides(args: &[String], known: &[&str]) -> Result<Vec<(String, String)>, Error> {
+    let mut overrides = vec![];
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg != "--param" {
+            continue;
+        }
+
+        let arg = args.next().map_or("", String::as_str);
+        let (name, value) = arg
+            .split_once('=')
+            .ok_or_else(|| Error::Syntax(arg.into()))?;
+        let (name, value) = (name.trim(), value.trim());
+
+        if !known.contains(&name) {
+            return Err(unknown(name, known));
+        }
+
+        overrides.push((name.to_string(), value.to_string()));
+    }
+
+    Ok(overrides)
+}
+
+/// Collect the overrides of a puzzle from the config. Parameters that are not
+/// one of the `known` parameters are returned as errors.
+pub fn from_config(
+    config: &Config,
+    puzzle: PuzzleId,
+    known: &[&str],
+) -> (Vec<(String, String)>, Vec<Error>) {
+    let mut overrides = vec![];
+    let mut errors = vec![];
+
+    for (name, value) in config.params(puzzle) {
+        if known.contains(&name) {
+            overrides.push((name.to_string(), value.to_string()));
+        } else {
+            errors.push(unknown(name, known));
+        }
+    }
+
+    (overrides, errors)
+}
+
+/// Read the overrides of the solution from the config, followed by the ones
+/// passed to it, so that the latter take precedence. Exits if a parameter
+/// passed to it is not one of the `known` parameters of the solution, and
+/// warns about configured ones.
+#[must_use]
+pub fn from_args(puzzle: PuzzleId, known: &[&str]) -> Vec<(String, String)> {
+    let (mut overrides, errors) = from_config(config::get(), puzzle, known);
+    for e in errors {
+        eprintln!("Warning: [params.{puzzle}] in the config: {e}");
+    }
+
+    let args: Vec<String> = env::args().collect();
+    match parse_overrides(&args, known) {
+        Ok(args) => {
+            overrides.extend(args);
+            overrides
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the value of a parameter into its field.
+pub fn set<T: FromStr>(field: &mut T, name: &str, value: &str) -> Result<(), Error> {
+    *field = value.parse().map_err(|_| Error::Value {
+        name: name.into(),
+        value: value.into(),
+    })?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_config, parse_overrides, set, unknown, Error};
+    use crate::template::config::Config;
+    use crate::{day, year, PuzzleId};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_overrides() {
+        let args = args(&["--time", "--param", "steps=5000", "--param", "n = 2"]);
+        assert_eq!(
+            parse_overrides(&args, &["steps", "n"]),
+            Ok(vec![
+                ("steps".into(), "5000".into()),
+                ("n".into(), "2".into())
+            ])
+        );
+        assert_eq!(parse_overrides(&args[..1], &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        assert_eq!(
+            parse_overrides(&args(&["--param", "steps"]), &["steps"]),
+            Err(Error::Syntax("steps".into()))
+        );
+        assert_eq!(
+            parse_overrides(&args(&["--param", "stepz=1"]), &["steps"]),
+            Err(unknown("stepz", &["steps"]))
+        );
+        assert_eq!(
+            unknown("steps", &[]).to_string(),
+            "unknown parameter \"steps\", this solution has no parameters"
+        );
+    }
+
+    #[test]
+    fn reads_config() {
+        let config = Config::layered(
+            Some("[params.2023-21]\nsteps = 100\nstepz = 1\n[params.2023-22]\nsteps = 5"),
+            vec![],
+        )
+        .unwrap();
+        let (overrides, errors) =
+            from_config(&config, PuzzleId::new(year!(2023), day!(21)), &["steps"]);
+        assert_eq!(overrides, vec![("steps".into(), "100".into())]);
+        assert_eq!(errors, vec![unknown("stepz", &["steps"])]);
+    }
+
+    #[test]
+    fn sets_values() {
+        let mut steps = 64_u64;
+        assert_eq!(set(&mut steps, "steps", "5000"), Ok(()));
+        assert_eq!(steps, 5000);
+        assert!(set(&mut steps, "steps", "-1").is_err());
+        assert_eq!(steps, 5000);
+    }
+}
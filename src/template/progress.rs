@@ -0,0 +1,342 @@
+/// Module that works out the progress of a year from the local data, and shows
+/// it as a stars table in the readme and as a calendar in the terminal.
+///
+/// A part earns its star when its answer is recorded in `data/YYYY/answers/`, or
+/// when the ledger holds a submission that the website accepted.
+use std::{fmt::Write, fs, path::Path};
+
+use crate::template::{
+    answers,
+    aoc_cli::get_puzzle_path,
+    ledger::{Entry, Ledger, Outcome},
+    readme_benchmarks::{self, get_path_for_bin, locate_table},
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// Marks the stars table of a year in the readme, like `aoc-readme-stars` does.
+fn marker(year: Year) -> String {
+    format!("<!--- advent_readme_stars table {year} --->")
+}
+
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Unsolved,
+    /// Submitted, but every answer was rejected.
+    Attempted {
+        rejected: usize,
+    },
+    Solved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// The title of the puzzle, if its description was downloaded.
+    pub title: Option<String>,
+    pub has_solution: bool,
+    pub parts: [PartStatus; 2],
+    /// Whether the tests of the solution pass, if they were run.
+    pub tests: Option<bool>,
+}
+
+impl DayProgress {
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| **p == PartStatus::Solved)
+            .count()
+    }
+
+    /// Whether the day shows up in the readme.
+    fn is_started(&self) -> bool {
+        self.has_solution || self.parts.iter().any(|p| *p != PartStatus::Unsolved)
+    }
+}
+
+/// The status of a part, from its recorded answer and its submissions.
+#[must_use]
+pub fn part_status<'a>(
+    recorded: Option<&str>,
+    submissions: impl IntoIterator<Item = &'a Entry>,
+) -> PartStatus {
+    if recorded.is_some() {
+        return PartStatus::Solved;
+    }
+
+    let mut rejected = 0;
+    for entry in submissions {
+        if entry.outcome == Outcome::Correct {
+            return PartStatus::Solved;
+        }
+        if entry.outcome.is_rejected() {
+            rejected += 1;
+        }
+    }
+
+    if rejected > 0 {
+        PartStatus::Attempted { rejected }
+    } else {
+        PartStatus::Unsolved
+    }
+}
+
+/// The title of a puzzle from its description, e.g. "Clumsy Crucible".
+fn parse_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let (_, title) = line.split_once("--- Day ")?.1.split_once(": ")?;
+        Some(title.trim_end().trim_end_matches("---").trim().to_string())
+    })
+}
+
+/// The second part of day 25 has no puzzle: its star comes with the other 49.
+fn award_last_star(days: &mut [DayProgress]) {
+    let stars: usize = days.iter().map(DayProgress::stars).sum();
+    if let Some(last) = days.iter_mut().find(|p| p.day == 25) {
+        if stars == 49 && last.parts[1] != PartStatus::Solved {
+            last.parts[1] = PartStatus::Solved;
+        }
+    }
+}
+
+/// Collect the progress of every day of a year. The tests are left unknown.
+#[must_use]
+pub fn collect(year: Year, ledger: &Ledger) -> Vec<DayProgress> {
+    let mut days: Vec<DayProgress> = all_days()
+        .map(|day| {
+            let puzzle = PuzzleId::new(year, day);
+            let part = |part: u8| {
+                part_status(
+                    answers::read(puzzle, part).as_deref(),
+                    ledger.entries(day, part),
+                )
+            };
+
+            DayProgress {
+                day,
+                title: fs::read_to_string(get_puzzle_path(puzzle))
+                    .ok()
+                    .and_then(|markdown| parse_title(&markdown)),
+                has_solution: Path::new(&get_path_for_bin(puzzle)).exists(),
+                parts: [part(1), part(2)],
+                tests: None,
+            }
+        })
+        .collect();
+
+    award_last_star(&mut days);
+    days
+}
+
+fn star(status: PartStatus) -> &'static str {
+    match status {
+        PartStatus::Solved => "⭐",
+        PartStatus::Unsolved | PartStatus::Attempted { .. } => " ",
+    }
+}
+
+fn construct_table(year: Year, days: &[DayProgress]) -> String {
+    let stars: usize = days.iter().map(DayProgress::stars).sum();
+
+    let mut lines: Vec<String> = vec![
+        marker(year),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for progress in days.iter().filter(|p| p.is_started()) {
+        let day = progress.day.into_inner();
+        let title = progress
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {day}"));
+        lines.push(format!(
+            "| [{title}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(progress.parts[0]),
+            star(progress.parts[1])
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+    lines.push(marker(year));
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    days: &[DayProgress],
+) -> Result<(), readme_benchmarks::Error> {
+    let positions = locate_table(s, &marker(year))?;
+    let table = construct_table(year, days);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: Year, days: &[DayProgress]) -> Result<(), readme_benchmarks::Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, days)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/// A calendar of the year for the terminal, with five days per row.
+#[must_use]
+pub fn calendar(year: Year, days: &[DayProgress]) -> String {
+    let stars: usize = days.iter().map(DayProgress::stars).sum();
+    let mut calendar = format!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET} · {stars}/50 ⭐\n");
+
+    for row in days.chunks(5) {
+        calendar.push('\n');
+        for progress in row {
+            let _ = write!(calendar, " {:>2} ", progress.day.into_inner());
+            for part in progress.parts {
+                calendar.push_str(&match part {
+                    PartStatus::Solved => format!("{ANSI_YELLOW}*{ANSI_RESET}"),
+                    PartStatus::Attempted { .. } => format!("{ANSI_RED}*{ANSI_RESET}"),
+                    PartStatus::Unsolved => format!("{ANSI_DIM}*{ANSI_RESET}"),
+                });
+            }
+            calendar.push_str(match progress.tests {
+                Some(true) => " ✔  ",
+                Some(false) => " ✘  ",
+                None => "    ",
+            });
+        }
+        calendar.truncate(calendar.trim_end().len());
+    }
+
+    calendar.push('\n');
+    calendar
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        award_last_star, construct_table, parse_title, part_status, update_content, DayProgress,
+        PartStatus,
+    };
+    use crate::template::ledger::{Entry, Outcome};
+    use crate::{all_days, day, year};
+
+    const MARKER: &str = "<!--- advent_readme_stars table 2023 --->";
+
+    fn entry(outcome: Outcome) -> Entry {
+        Entry {
+            day: day!(1),
+            part: 1,
+            answer: "42".into(),
+            outcome,
+            timestamp: 0,
+            wait_until: None,
+        }
+    }
+
+    fn progress(parts: [PartStatus; 2]) -> Vec<DayProgress> {
+        all_days()
+            .map(|day| DayProgress {
+                day,
+                title: (day == 1).then(|| "Trebuchet?!".into()),
+                has_solution: day <= 2,
+                parts: if day == 1 {
+                    parts
+                } else {
+                    [PartStatus::Unsolved; 2]
+                },
+                tests: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn determines_part_status() {
+        assert_eq!(part_status(Some("42"), &[]), PartStatus::Solved);
+        assert_eq!(part_status(None, &[]), PartStatus::Unsolved);
+        assert_eq!(
+            part_status(None, &[entry(Outcome::TooLow), entry(Outcome::TooSoon)]),
+            PartStatus::Attempted { rejected: 1 }
+        );
+        assert_eq!(
+            part_status(None, &[entry(Outcome::TooLow), entry(Outcome::Correct)]),
+            PartStatus::Solved
+        );
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("article\n\n\\--- Day 17: Clumsy Crucible ---\n----------\n").as_deref(),
+            Some("Clumsy Crucible")
+        );
+        assert_eq!(
+            parse_title("## --- Day 1: Trebuchet?! ---\n").as_deref(),
+            Some("Trebuchet?!")
+        );
+        assert_eq!(parse_title("## --- Part Two ---\n"), None);
+    }
+
+    #[test]
+    fn constructs_table() {
+        let table = construct_table(
+            year!(2023),
+            &progress([PartStatus::Solved, PartStatus::Unsolved]),
+        );
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[1], "## 2023 Results");
+        assert_eq!(
+            lines[5],
+            "| [Trebuchet?!](https://adventofcode.com/2023/day/1) | ⭐ |   |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](https://adventofcode.com/2023/day/2) |   |   |"
+        );
+        assert_eq!(lines[8], "**Stars: 1/50**");
+    }
+
+    #[test]
+    fn replaces_single_marker() {
+        let mut s = format!("# AoC\n\n{MARKER}\n\nmore");
+        update_content(&mut s, year!(2023), &progress([PartStatus::Solved; 2])).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("**Stars: 2/50**"));
+        assert!(s.ends_with(&format!("{MARKER}\n\nmore")));
+
+        update_content(&mut s, year!(2023), &progress([PartStatus::Unsolved; 2])).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("**Stars: 0/50**"));
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let other = "<!--- advent_readme_stars table 2022 --->";
+        let mut s = format!("{other}\n## 2022 Results\n{other}\n\n{MARKER}");
+        update_content(&mut s, year!(2023), &progress([PartStatus::Solved; 2])).unwrap();
+        assert!(s.starts_with(&format!("{other}\n## 2022 Results\n{other}\n\n{MARKER}")));
+        assert!(s.contains("## 2023 Results"));
+        assert!(update_content(&mut s, year!(2021), &[]).is_err());
+    }
+
+    #[test]
+    fn awards_last_star() {
+        let mut days = progress([PartStatus::Solved; 2]);
+        award_last_star(&mut days);
+        assert_eq!(days[24].parts[1], PartStatus::Unsolved);
+
+        days.iter_mut()
+            .for_each(|p| p.parts = [PartStatus::Solved; 2]);
+        days[24].parts[1] = PartStatus::Unsolved;
+        award_last_star(&mut days);
+        assert_eq!(days[24].stars(), 2);
+    }
+}
@@ -1,10 +1,11 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Every year has its own table, between a pair of `<!--- benchmarking table YYYY --->` markers.
+use std::{fmt::Display, fs, io, time::Duration};
 
-use crate::Day;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::report::{PartReport, PARSE_PART};
+use crate::{Day, PuzzleId, Year};
 
 #[derive(Debug)]
 pub enum Error {
@@ -18,26 +19,95 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// The timing of a part: the median duration, and optionally the spread of
+/// the samples around it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartTiming {
+    pub duration: Duration,
+    pub spread: Option<Duration>,
+}
+
+impl From<Duration> for PartTiming {
+    fn from(duration: Duration) -> Self {
+        PartTiming {
+            duration,
+            spread: None,
+        }
+    }
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.spread {
+            Some(spread) => write!(f, "{:.1?} ± {:.1?}", self.duration, spread),
+            None => write!(f, "{:.1?}", self.duration),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// The shared parse step, for solutions that have one.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Collect the timings of the parts of a day that were benched.
+    #[must_use]
+    pub fn from_reports(day: Day, reports: &[PartReport]) -> Self {
+        let mut timings = Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        reports
+            .iter()
+            .filter(|r| r.day == day && (r.answer.is_some() || r.is_parse()) && r.is_benched())
+            .for_each(|r| {
+                let timing = PartTiming {
+                    duration: r.duration,
+                    spread: r.stats.map(|s| s.std_dev),
+                };
+                match r.part {
+                    PARSE_PART => timings.parse = Some(timing),
+                    1 => timings.part_1 = Some(timing),
+                    2 => timings.part_2 = Some(timing),
+                    _ => return,
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                let nanos = r.duration.as_nanos() as f64;
+                timings.total_nanos += nanos;
+            });
+
+        timings
+    }
+}
+
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
+}
+
+fn marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Find the table between a pair of markers. A single marker is an empty table.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,73 +128,177 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Vec<Timings>, total_millis: f64) -> String {
+    let header = format!("{prefix} Benchmarks {year}");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker(year),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.parse.map_or_else(|| "-".into(), |t| t.to_string()),
+            timing.part_1.map_or_else(|| "-".into(), |t| t.to_string()),
+            timing.part_2.map_or_else(|| "-".into(), |t| t.to_string())
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker(year))?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use std::time::Duration;
+
+    use super::{update_content, PartTiming, Timings};
+    use crate::{day, year};
+
+    const MARKER: &str = "<!--- benchmarking table 2023 --->";
+    use crate::template::{report::PartReport, stats::BenchStats};
+
+    fn report(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartReport {
+        let duration = Duration::from_nanos(nanos);
+        PartReport {
+            day: day!(1),
+            part,
+            answer: answer.map(String::from),
+            duration,
+            stats: (samples > 1).then_some(BenchStats {
+                samples,
+                min: duration,
+                median: duration,
+                mean: duration,
+                p95: duration,
+                std_dev: duration / 10,
+            }),
+            mem: None,
+            verdict: None,
+            failure: None,
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn collects_well_formed_timings() {
+        let res = Timings::from_reports(
+            day!(1),
+            &[
+                report(1, Some("0"), 74, 100000),
+                report(2, Some("10"), 74_130_000, 99999),
+            ],
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap().to_string(), "74.0ns ± 7.0ns");
+        assert_eq!(
+            res.part_2.unwrap(),
+            PartTiming {
+                duration: Duration::from_nanos(74_130_000),
+                spread: Some(Duration::from_nanos(7_413_000))
+            }
+        );
+    }
+
+    #[test]
+    fn collects_parse_timings() {
+        let res = Timings::from_reports(
+            day!(1),
+            &[report(0, None, 1_000, 10), report(1, Some("0"), 2_000, 10)],
+        );
+        assert_approx_eq!(res.total_nanos, 3000_f64);
+        assert_eq!(res.parse.unwrap().duration, Duration::from_micros(1));
+        assert_eq!(res.part_1.unwrap().duration, Duration::from_micros(2));
+    }
+
+    #[test]
+    fn skips_unbenched_parts() {
+        let res = Timings::from_reports(
+            day!(1),
+            &[
+                report(1, Some("0"), 2_000_000_000, 5),
+                report(2, Some("10s"), 100_000_000, 1),
+            ],
+        );
+        assert_approx_eq!(res.total_nanos, 2000000000_f64);
+        assert_eq!(res.part_1.unwrap().duration, Duration::from_secs(2));
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    #[test]
+    fn skips_missing_parts() {
+        let res =
+            Timings::from_reports(day!(1), &[report(1, None, 10, 10), report(2, None, 10, 10)]);
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                part_1: Some(Duration::from_millis(10).into()),
+                part_2: Some(Duration::from_millis(20).into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: Some(Duration::from_millis(5).into()),
+                part_1: Some(Duration::from_millis(30).into()),
+                part_2: Some(Duration::from_millis(40).into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                part_1: Some(Duration::from_millis(40).into()),
+                part_2: Some(Duration::from_millis(50).into()),
                 total_nanos: 9e+10,
             },
         ]
@@ -134,53 +308,80 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn updates_table_of_year() {
+        let other = "<!--- benchmarking table 2022 --->";
+        let mut s = format!("{other}\n## Benchmarks 2022\n{other}\n{MARKER}{MARKER}");
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert!(s.starts_with(&format!("{other}\n## Benchmarks 2022\n{other}\n")));
+        assert_eq!(s.matches("## Benchmarks 2023").count(), 1);
+        assert!(update_content(&mut s, year!(2021), get_mock_timings(), 190.0).is_err());
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## Benchmarks 2023",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_spread() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let timings = vec![Timings {
+            day: day!(17),
+            parse: None,
+            part_1: Some(PartTiming {
+                duration: Duration::from_micros(22_200),
+                spread: Some(Duration::from_micros(1_300)),
+            }),
+            part_2: None,
+            total_nanos: 2.22e+7,
+        }];
+        update_content(&mut s, year!(2023), timings, 22.2).unwrap();
+        assert!(s.contains("| [Day 17](./src/bin/2023-17.rs) | `-` | `22.2ms ± 1.3ms` | `-` |"));
+    }
 }
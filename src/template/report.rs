@@ -0,0 +1,507 @@
+/// The result of running one part of a solution, and its machine-readable
+/// representation. With `--format json`, the runner prints one record per part
+/// as a single line of JSON:
+///
+/// ```text
+/// {"day":1,"part":1,"answer":"142","duration_ns":1200,"samples":1,"min_ns":null,"median_ns":null,"mean_ns":null,"p95_ns":null,"std_dev_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null,"status":"solved","expected":null,"panic":null,"profile":null}
+/// ```
+///
+/// `duration_ns` is the median duration if the part was benched. The other
+/// statistics of the samples are only set in that case. Likewise, `allocations`,
+/// `allocated_bytes` and `peak_bytes` are only set when running with `--mem`.
+///
+/// `status` is one of `solved`, `unsolved`, `correct`, `wrong`, `unrecorded`,
+/// `panicked` or `timed_out`. `correct`, `wrong` and `unrecorded` are only used
+/// when checking against recorded answers, and `expected` is only set for
+/// `wrong`. `panic` holds the message of a part that panicked.
+///
+/// With `--profile`, `profile` holds the spans of the part as
+/// `path:duration_ns:calls`, separated by `;`, see [`super::trace`].
+///
+/// Solutions with a shared parse step also print a record for it, with `part`
+/// set to `0`. It has no answer and its status is always `solved`.
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{
+    answers::Verdict,
+    json::{self, Value},
+    memory::AllocStats,
+    stats::BenchStats,
+    trace::Profile,
+};
+use crate::Day;
+
+/// How the runner prints its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => f.write_str("text"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of either \"text\" or \"json\"")
+    }
+}
+
+/// Why a part has no result, other than the solution returning `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panicked(String),
+    /// The solution ran out of the time given with `--timeout`.
+    TimedOut,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "✖ panicked: {message}"),
+            Failure::TimedOut => write!(f, "⏱ timed out"),
+        }
+    }
+}
+
+/// Summary of the outcome of a part. A failure takes precedence over a
+/// regression, which takes precedence over a missing answer, which takes
+/// precedence over the other outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    Correct,
+    Wrong,
+    Unrecorded,
+    Panicked,
+    TimedOut,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unrecorded => "unrecorded",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "correct" => Ok(Status::Correct),
+            "wrong" => Ok(Status::Wrong),
+            "unrecorded" => Ok(Status::Unrecorded),
+            "panicked" => Ok(Status::Panicked),
+            "timed_out" => Ok(Status::TimedOut),
+            _ => Err(Error::Field("status")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Json(json::ParseError),
+    Field(&'static str),
+}
+
+impl From<json::ParseError> for Error {
+    fn from(e: json::ParseError) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Json(e) => write!(f, "{e}"),
+            Error::Field(name) => write!(f, "missing or invalid field \"{name}\""),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The part number used in the report of a solution's parse step.
+pub const PARSE_PART: u8 = 0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    /// Statistics of the samples, if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// The allocations of the part, if they were counted.
+    pub mem: Option<AllocStats>,
+    pub verdict: Option<Verdict>,
+    pub failure: Option<Failure>,
+    /// The spans of the part, if it was profiled.
+    pub profile: Option<Profile>,
+}
+
+impl PartReport {
+    pub fn status(&self) -> Status {
+        match &self.failure {
+            Some(Failure::Panicked(_)) => return Status::Panicked,
+            Some(Failure::TimedOut) => return Status::TimedOut,
+            None => {}
+        }
+
+        if self.is_parse() {
+            return Status::Solved;
+        }
+
+        match (&self.answer, &self.verdict) {
+            (_, Some(Verdict::Wrong { .. })) => Status::Wrong,
+            (None, _) => Status::Unsolved,
+            (Some(_), Some(Verdict::Correct)) => Status::Correct,
+            (Some(_), Some(Verdict::Unrecorded)) => Status::Unrecorded,
+            (Some(_), None) => Status::Solved,
+        }
+    }
+
+    /// Whether this reports the parse step rather than one of the parts.
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE_PART
+    }
+
+    /// Whether the result differs from the recorded answer.
+    pub fn is_regression(&self) -> bool {
+        self.status() == Status::Wrong
+    }
+
+    /// Whether the part panicked or timed out.
+    pub fn is_failure(&self) -> bool {
+        self.failure.is_some()
+    }
+
+    /// Whether the part was benched, as opposed to executed a single time.
+    pub fn is_benched(&self) -> bool {
+        self.stats.is_some()
+    }
+
+    /// The number of times the part was executed to measure its duration.
+    pub fn samples(&self) -> u128 {
+        self.stats.map_or(1, |s| s.samples)
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let expected = match &self.verdict {
+            Some(Verdict::Wrong { expected }) => Some(expected.as_str()),
+            _ => None,
+        };
+        let panic = match &self.failure {
+            Some(Failure::Panicked(message)) => Some(message.as_str()),
+            _ => None,
+        };
+        let stat = |get: fn(&BenchStats) -> Duration| {
+            self.stats
+                .as_ref()
+                .map_or(Value::Null, |s| Value::from(get(s).as_nanos()))
+        };
+        let mem = |get: fn(&AllocStats) -> u64| {
+            self.mem
+                .as_ref()
+                .map_or(Value::Null, |m| Value::from(u128::from(get(m))))
+        };
+
+        json::write_object(&[
+            ("day", Value::from(self.day.into_inner())),
+            ("part", Value::from(self.part)),
+            ("answer", Value::from(self.answer.as_deref())),
+            ("duration_ns", Value::from(self.duration.as_nanos())),
+            ("samples", Value::from(self.samples())),
+            ("min_ns", stat(|s| s.min)),
+            ("median_ns", stat(|s| s.median)),
+            ("mean_ns", stat(|s| s.mean)),
+            ("p95_ns", stat(|s| s.p95)),
+            ("std_dev_ns", stat(|s| s.std_dev)),
+            ("allocations", mem(|m| m.allocations)),
+            ("allocated_bytes", mem(|m| m.allocated_bytes)),
+            ("peak_bytes", mem(|m| m.peak_bytes)),
+            ("status", Value::from(self.status().as_str())),
+            ("expected", Value::from(expected)),
+            ("panic", Value::from(panic)),
+            (
+                "profile",
+                Value::from(self.profile.as_ref().map(ToString::to_string).as_deref()),
+            ),
+        ])
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let object = json::parse_object(s)?;
+        let field = |name: &'static str| object.get(name).ok_or(Error::Field(name));
+
+        let day = field("day")?
+            .as_u64()
+            .and_then(|d| u8::try_from(d).ok())
+            .and_then(Day::new)
+            .ok_or(Error::Field("day"))?;
+        let part = field("part")?
+            .as_u64()
+            .and_then(|p| u8::try_from(p).ok())
+            .ok_or(Error::Field("part"))?;
+        let answer = match field("answer")? {
+            Value::Null => None,
+            value => Some(value.as_str().ok_or(Error::Field("answer"))?.to_string()),
+        };
+        let duration = field("duration_ns")?
+            .as_u64()
+            .map(Duration::from_nanos)
+            .ok_or(Error::Field("duration_ns"))?;
+        let samples = field("samples")?.as_u128().ok_or(Error::Field("samples"))?;
+        let stat = |name: &'static str| -> Result<Option<Duration>, Error> {
+            match object.get(name) {
+                None | Some(Value::Null) => Ok(None),
+                Some(value) => value
+                    .as_u64()
+                    .map(|n| Some(Duration::from_nanos(n)))
+                    .ok_or(Error::Field(name)),
+            }
+        };
+        let stats = match stat("min_ns")? {
+            None => None,
+            Some(min) => Some(BenchStats {
+                samples,
+                min,
+                median: stat("median_ns")?.ok_or(Error::Field("median_ns"))?,
+                mean: stat("mean_ns")?.ok_or(Error::Field("mean_ns"))?,
+                p95: stat("p95_ns")?.ok_or(Error::Field("p95_ns"))?,
+                std_dev: stat("std_dev_ns")?.ok_or(Error::Field("std_dev_ns"))?,
+            }),
+        };
+        let count = |name: &'static str| -> Result<Option<u64>, Error> {
+            match object.get(name) {
+                None | Some(Value::Null) => Ok(None),
+                Some(value) => value.as_u64().map(Some).ok_or(Error::Field(name)),
+            }
+        };
+        let mem = match count("allocations")? {
+            None => None,
+            Some(allocations) => Some(AllocStats {
+                allocations,
+                allocated_bytes: count("allocated_bytes")?
+                    .ok_or(Error::Field("allocated_bytes"))?,
+                peak_bytes: count("peak_bytes")?.ok_or(Error::Field("peak_bytes"))?,
+            }),
+        };
+        let profile = match object.get("profile") {
+            None | Some(Value::Null) => None,
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or(Error::Field("profile"))?,
+            ),
+        };
+        let status: Status = field("status")?
+            .as_str()
+            .ok_or(Error::Field("status"))?
+            .parse()?;
+
+        let failure = match status {
+            Status::Panicked => {
+                let message = field("panic")?.as_str().ok_or(Error::Field("panic"))?;
+                Some(Failure::Panicked(message.to_string()))
+            }
+            Status::TimedOut => Some(Failure::TimedOut),
+            _ => None,
+        };
+
+        let verdict = match status {
+            Status::Solved | Status::Unsolved | Status::Panicked | Status::TimedOut => None,
+            Status::Correct => Some(Verdict::Correct),
+            Status::Unrecorded => Some(Verdict::Unrecorded),
+            Status::Wrong => {
+                let expected = field("expected")?
+                    .as_str()
+                    .ok_or(Error::Field("expected"))?;
+                Some(Verdict::Wrong {
+                    expected: expected.to_string(),
+                })
+            }
+        };
+
+        Ok(PartReport {
+            day,
+            part,
+            answer,
+            duration,
+            stats,
+            mem,
+            verdict,
+            failure,
+            profile,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Failure, PartReport, Status};
+    use crate::day;
+    use crate::template::{
+        answers::Verdict,
+        memory::AllocStats,
+        stats::BenchStats,
+        trace::{Profile, SpanStats},
+    };
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            day: day!(17),
+            part: 2,
+            answer: Some("1\n2".into()),
+            duration: Duration::from_nanos(96_500_000),
+            stats: Some(BenchStats {
+                samples: 11,
+                min: Duration::from_nanos(90_000_000),
+                median: Duration::from_nanos(96_500_000),
+                mean: Duration::from_nanos(97_000_000),
+                p95: Duration::from_nanos(110_000_000),
+                std_dev: Duration::from_nanos(4_000_000),
+            }),
+            mem: Some(AllocStats {
+                allocations: 12,
+                allocated_bytes: 4096,
+                peak_bytes: 1024,
+            }),
+            verdict: Some(Verdict::Wrong {
+                expected: "71".into(),
+            }),
+            failure: None,
+            profile: Some(Profile {
+                spans: vec![SpanStats {
+                    path: "search".into(),
+                    duration: Duration::from_nanos(90_000_000),
+                    calls: 1,
+                }],
+            }),
+        }
+    }
+
+    #[test]
+    fn formats_json() {
+        assert_eq!(
+            get_mock_report().to_json(),
+            concat!(
+                r#"{"day":17,"part":2,"answer":"1\n2","duration_ns":96500000,"samples":11,"#,
+                r#""min_ns":90000000,"median_ns":96500000,"mean_ns":97000000,"p95_ns":110000000,"#,
+                r#""std_dev_ns":4000000,"allocations":12,"allocated_bytes":4096,"peak_bytes":1024,"#,
+                r#""status":"wrong","expected":"71","panic":null,"profile":"search:90000000:1"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let report = get_mock_report();
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+
+        let unsolved = PartReport {
+            answer: None,
+            verdict: None,
+            stats: None,
+            mem: None,
+            profile: None,
+            ..report
+        };
+        let parsed = PartReport::from_json(&unsolved.to_json()).unwrap();
+        assert_eq!(parsed.status(), Status::Unsolved);
+        assert_eq!(parsed, unsolved);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert!(PartReport::from_json("Part 1: 42 (1.0ms)").is_err());
+        assert!(PartReport::from_json(r#"{"day":26,"part":1}"#).is_err());
+        assert!(PartReport::from_json(
+            r#"{"day":1,"part":1,"answer":null,"duration_ns":1,"samples":1,"status":"bad"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn round_trips_parse_step() {
+        let parse = PartReport {
+            part: 0,
+            answer: None,
+            verdict: None,
+            ..get_mock_report()
+        };
+        assert_eq!(parse.status(), Status::Solved);
+        assert!(parse.to_json().contains(r#""part":0,"answer":null"#));
+        assert_eq!(PartReport::from_json(&parse.to_json()).unwrap(), parse);
+    }
+
+    #[test]
+    fn round_trips_failures() {
+        let panicked = PartReport {
+            answer: None,
+            verdict: None,
+            failure: Some(Failure::Panicked("index out of bounds".into())),
+            ..get_mock_report()
+        };
+        assert_eq!(panicked.status(), Status::Panicked);
+        assert!(panicked
+            .to_json()
+            .contains(r#""status":"panicked","expected":null,"panic":"index out of bounds""#));
+        assert_eq!(
+            PartReport::from_json(&panicked.to_json()).unwrap(),
+            panicked
+        );
+
+        let timed_out = PartReport {
+            part: 0,
+            failure: Some(Failure::TimedOut),
+            ..panicked
+        };
+        assert_eq!(timed_out.status(), Status::TimedOut);
+        assert_eq!(
+            PartReport::from_json(&timed_out.to_json()).unwrap(),
+            timed_out
+        );
+        assert_eq!(timed_out.failure.unwrap().to_string(), "⏱ timed out");
+    }
+}
@@ -1,63 +1,494 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::aoc_client::{self, Backend, Submission};
+use crate::template::ledger::{self, Entry, Ledger};
+use crate::template::memory::{self, AllocStats};
+use crate::template::report::{Failure, OutputFormat, PartReport, PARSE_PART};
+use crate::template::stats::BenchStats;
+use crate::template::trace::{self, Profile};
+use crate::template::{
+    answers, config, parse_duration, read_file, read_input_from, ReadError, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+use std::any::Any;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The part that is running, which the `--timeout` watchdog reports.
+static CURRENT_PART: AtomicU8 = AtomicU8::new(PARSE_PART);
+
+/// Set when the results are not the answers to the puzzle, see [`mark_custom_run`].
+static IS_CUSTOM_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Note that the results of this run are not the answers to the puzzle, e.g.
+/// because of `--input`. `reason` completes "can not submit results ...".
+/// Exits if `--submit` was passed, and skips `--check`, as the recorded answers
+/// do not apply.
+pub fn mark_custom_run(reason: &str) {
+    if env::args().any(|x| x == "--submit") {
+        eprintln!("Error: can not submit results {reason}.");
+        process::exit(1);
+    }
+
+    let was_custom = IS_CUSTOM_RUN.swap(true, Ordering::Relaxed);
+    if !was_custom && env::args().any(|x| x == "--check") {
+        eprintln!("Warning: not checking results {reason}.");
+    }
+}
+
+/// Run and print one part of a solution. With `--check`, the result is compared
+/// to the recorded answer. A panic of the part is reported as its failure.
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> PartReport {
+    let format = output_format();
     let part_str = format!("Part {part}");
+    CURRENT_PART.store(part, Ordering::Relaxed);
+
+    let (result, measurement, failure) = match catch_panic(|| {
+        run_timed(func, input, |result| {
+            if format == OutputFormat::Text {
+                print_result(result, &part_str, "", "");
+            }
+        })
+    }) {
+        Ok((result, measurement)) => (result, measurement, None),
+        Err(failure) => (None, Measurement::default(), Some(failure)),
+    };
+
+    let answer = result.as_ref().map(ToString::to_string);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let is_checked = env::args().any(|x| x == "--check") && !IS_CUSTOM_RUN.load(Ordering::Relaxed);
+    let verdict = if is_checked {
+        Some(answers::check(puzzle, part, answer.as_deref()))
+    } else {
+        None
+    };
+
+    let report = PartReport {
+        day: puzzle.day,
+        part,
+        answer,
+        duration: measurement.duration,
+        stats: measurement.stats,
+        mem: measurement.mem,
+        verdict,
+        failure,
+        profile: measurement.profile,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    match format {
+        OutputFormat::Text => print_report(&report),
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part);
     }
+
+    report
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+/// Run and print the parse step of a solution, whose result is shared by both
+/// parts. Returns the parsed input together with the report of the step, or
+/// no input if the step panicked.
+pub fn run_parse<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    puzzle: PuzzleId,
+) -> (Option<T>, PartReport) {
+    let format = output_format();
+    CURRENT_PART.store(PARSE_PART, Ordering::Relaxed);
+
+    let (parsed, measurement, failure) = match catch_panic(|| {
+        run_timed(func, input, |_| {
+            if format == OutputFormat::Text {
+                print!("Parse:");
+                let _ = stdout().flush();
+            }
+        })
+    }) {
+        Ok((parsed, measurement)) => (Some(parsed), measurement, None),
+        Err(failure) => (None, Measurement::default(), Some(failure)),
+    };
+
+    let report = PartReport {
+        day: puzzle.day,
+        part: PARSE_PART,
+        answer: None,
+        duration: measurement.duration,
+        stats: measurement.stats,
+        mem: measurement.mem,
+        verdict: None,
+        failure,
+        profile: measurement.profile,
+    };
+
+    match format {
+        OutputFormat::Text => print_report(&report),
+        OutputFormat::Json => println!("{}", report.to_json()),
+    }
+
+    (parsed, report)
+}
+
+/// Run a step of a solution, turning a panic into a [`Failure`]. The panic
+/// message is still printed to stderr, together with its location.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(func))
+        .map_err(|payload| Failure::Panicked(panic_message(payload.as_ref())))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Exit once the time passed with `--timeout` is up, reporting the part that
+/// was running as timed out. The solution stops itself rather than being
+/// killed, because killing `cargo run` would leave the solution running. The
+/// `all` command only kills the ones that fail to stop.
+pub fn start_timeout(puzzle: PuzzleId) {
+    let Some(timeout) = parse_arg(
+        "--timeout",
+        |x| parse_duration(x).ok(),
+        "cargo solve 1 --timeout 10s",
+    ) else {
+        return;
+    };
+    let format = output_format();
+
+    thread::spawn(move || {
+        thread::sleep(timeout);
+
+        let report = PartReport {
+            day: puzzle.day,
+            part: CURRENT_PART.load(Ordering::Relaxed),
+            answer: None,
+            duration: timeout,
+            stats: None,
+            mem: None,
+            verdict: None,
+            failure: Some(Failure::TimedOut),
+            profile: None,
+        };
+
+        match format {
+            OutputFormat::Text => print_report(&report),
+            OutputFormat::Json => println!("{}", report.to_json()),
+        }
+        process::exit(1);
+    });
+}
+
+/// Print the final result of a part in a human-readable format.
+pub fn print_report(report: &PartReport) {
+    if let Some(failure) = &report.failure {
+        let part = if report.is_parse() {
+            "Parse".to_string()
+        } else {
+            format!("Part {}", report.part)
+        };
+        print!("\r");
+        println!(
+            "{part}: {failure}{}",
+            format_verdict(report.verdict.as_ref())
+        );
+        return;
+    }
+
+    if report.is_parse() {
+        print!("\r");
+        println!(
+            "Parse:{}",
+            format_duration(&report.duration, report.stats.as_ref())
+        );
+    } else {
+        print_result(
+            &report.answer,
+            &format!("Part {}", report.part),
+            &format_duration(&report.duration, report.stats.as_ref()),
+            &format_verdict(report.verdict.as_ref()),
+        );
+    }
+
+    if let Some(mem) = &report.mem {
+        println!("        {ANSI_ITALIC}{mem}{ANSI_RESET}");
+    }
+
+    if let Some(stats) = &report.stats {
+        println!(
+            "        {ANSI_ITALIC}min {:.1?} · mean {:.1?} · p95 {:.1?}{ANSI_RESET}",
+            stats.min, stats.mean, stats.p95
+        );
+    }
+
+    if let Some(profile) = report.profile.as_ref().filter(|p| !p.spans.is_empty()) {
+        println!(
+            "{ANSI_ITALIC}{}{ANSI_RESET}",
+            profile.format_tree("        ")
+        );
+    }
+}
+
+/// Parse the value of an argument passed to the solution, e.g. `--format json`.
+/// Exits with a usage hint if the value is invalid.
+fn parse_arg<T>(name: &str, parse: impl Fn(&str) -> Option<T>, usage: &str) -> Option<T> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == name)?;
+
+    match args.get(index + 1).and_then(|x| parse(x)) {
+        Some(value) => Some(value),
+        None => {
+            eprintln!("Unexpected command-line input. Format: {usage}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The input that a solution runs on.
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub text: String,
+    /// Whether the input was passed with `--input`, so that the results are not
+    /// the answers to the puzzle.
+    pub is_custom: bool,
+}
+
+/// Read the input of a puzzle from `data/YYYY/inputs`, or from the file passed
+/// with `--input`. `--input -` reads the input from stdin.
+pub fn read_input(puzzle: PuzzleId) -> Result<Input, ReadError> {
+    match parse_arg(
+        "--input",
+        |x| Some(x.to_string()),
+        "cargo solve 1 --input path/to/file",
+    ) {
+        Some(path) => Ok(Input {
+            text: read_input_from(&path)?,
+            is_custom: true,
+        }),
+        None => Ok(Input {
+            text: read_file("inputs", puzzle)?,
+            is_custom: false,
+        }),
+    }
+}
+
+/// Parse the `--format` argument passed to the solution.
+#[must_use]
+pub fn output_format() -> OutputFormat {
+    parse_arg(
+        "--format",
+        |x| x.parse().ok(),
+        "cargo solve 1 --format json",
+    )
+    .unwrap_or_default()
+}
+
+/// How long to bench a part, from the `--samples` and `--bench-time` arguments.
+/// Unset values fall back to the `bench` settings of the config.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BenchSettings {
+    /// A fixed number of samples, overriding the time budget.
+    pub samples: Option<u128>,
+    /// The time budget, 1 second by default.
+    pub time: Option<Duration>,
+}
+
+impl BenchSettings {
+    fn from_args() -> Self {
+        let samples = parse_arg(
+            "--samples",
+            |x| x.parse().ok().filter(|&n| n > 0),
+            "cargo solve 1 --time --samples 100",
+        );
+        let time = parse_arg(
+            "--bench-time",
+            |x| parse_duration(x).ok(),
+            "cargo solve 1 --time --bench-time 5s",
+        );
+
+        BenchSettings { samples, time }
+    }
+
+    /// The arguments to pass these settings on to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(samples) = self.samples {
+            args.push("--samples".into());
+            args.push(samples.to_string());
+        }
+
+        if let Some(time) = self.time {
+            args.push("--bench-time".into());
+            args.push(format!("{}ns", time.as_nanos()));
+        }
+
+        args
+    }
+}
+
+/// The flags that the `solve` and `all` commands pass on to the runner of a
+/// solution.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunSettings {
+    /// Bench the parts, see `--time`.
+    pub time: bool,
+    pub bench: BenchSettings,
+    /// Compare the results to the recorded answers, see `--check`.
+    pub check: bool,
+    /// Count the allocations of the parts, see `--mem`.
+    pub mem: bool,
+    /// Stop the solution after this long, see `--timeout`.
+    pub timeout: Option<Duration>,
+    /// Record the spans of the parts, see `--profile`.
+    pub profile: bool,
+}
+
+impl RunSettings {
+    /// The arguments to pass these settings on to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+            args.extend(self.bench.to_args());
+        }
+
+        if self.check {
+            args.push("--check".into());
+        }
+
+        if self.mem {
+            args.push("--mem".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(format!("{}ns", timeout.as_nanos()));
+        }
+
+        if self.profile {
+            args.push("--profile".into());
+        }
+
+        args
+    }
+
+    /// The cargo features that a solution must be built with for these settings.
+    #[must_use]
+    pub fn features(&self) -> Vec<String> {
+        let mut features = vec![];
+
+        if self.mem {
+            features.push("mem".into());
+        }
+
+        features
+    }
+}
+
+/// What was measured while running a part.
+#[derive(Debug, Default)]
+struct Measurement {
+    duration: Duration,
+    stats: Option<BenchStats>,
+    mem: Option<AllocStats>,
+    profile: Option<Profile>,
+}
+
+/// Run a solution part. The behavior differs depending on whether the `--time` flag is passed:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched after a few warmup iterations (approx. 1 second of execution time or
+///     10 samples, whatever takes longer, unless `--bench-time` or `--samples` say otherwise). The returned
+///     duration is the median of the samples.
+///
+/// With `--mem`, the allocations of the first execution are counted as well, and
+/// with `--profile` its spans are recorded.
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
+    let is_mem = env::args().any(|x| x == "--mem");
+    let is_profile = env::args().any(|x| x == "--profile");
+
     let timer = Instant::now();
-    let result = func(input.clone());
+    let ((result, mem), profile) = if is_profile {
+        let (result, profile) = trace::record(|| measure_mem(is_mem, || func(input.clone())));
+        (result, Some(profile))
+    } else {
+        (measure_mem(is_mem, || func(input.clone())), None)
+    };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, stats) = if std::env::args().any(|x| x == "--time") {
+        let stats = bench(func, input, &base_time);
+        (stats.median, Some(stats))
     } else {
-        (base_time, 1)
+        (base_time, None)
     };
 
-    (result, run.0, run.1)
+    let measurement = Measurement {
+        duration,
+        stats,
+        mem,
+        profile,
+    };
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Count the allocations of a function, if `is_mem` is set. Only solutions that
+/// are built with the `mem` feature install the counting allocator.
+fn measure_mem<T>(is_mem: bool, func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if is_mem && !cfg!(feature = "mem") {
+        eprintln!("--mem requires the mem feature. Try: cargo solve 1 --mem");
+        process::exit(1);
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if is_mem {
+        let (result, mem) = memory::measure(func);
+        (result, Some(mem))
+    } else {
+        (func(), None)
+    }
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
+    if output_format() == OutputFormat::Text {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let settings = BenchSettings::from_args();
+    let config = &config::get().bench;
+    let bench_time = settings
+        .time
+        .or(config.time)
+        .unwrap_or(Duration::from_secs(1));
+    let bench_iterations = settings.samples.or(config.samples).unwrap_or_else(|| {
+        (bench_time.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
+    });
+
+    // warm up caches and the branch predictor before taking samples.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,36 +500,33 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).expect("at least one sample is taken")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} ± {:.1?} @ {} samples)",
+            stats.std_dev, stats.samples
+        ),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_verdict(verdict: Option<&answers::Verdict>) -> String {
+    match verdict {
+        Some(verdict) => format!(" {verdict}"),
+        None => String::new(),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, verdict_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼ {duration_str}{verdict_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -107,7 +535,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str =
+                    format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}{verdict_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -121,20 +550,21 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{verdict_str}             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution
+/// through the built-in client, or through aoc-cli if no session token is configured.
+/// Answers are checked against the submission ledger first, and the response of
+/// the website is recorded in it.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Submission, aoc_client::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +587,58 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+
+    // refuse answers that are known to be wrong, without asking the website.
+    let mut ledger = match Ledger::load(puzzle.year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Could not read the submission ledger: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = ledger.check(puzzle.day, part, &answer, ledger::now()) {
+        eprintln!("Not submitting {answer}: {refusal}.");
+        return None;
+    }
+
+    let backend = match Backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Could not submit: {e}");
+            eprintln!(
+                "Configure a session token, or install aoc-cli with \"cargo install aoc-cli\"."
+            );
+            process::exit(1);
+        }
+    };
+
+    // with `--format json`, stdout is reserved for the reports.
+    let notify = |message: String| match output_format() {
+        OutputFormat::Text => println!("{message}"),
+        OutputFormat::Json => eprintln!("{message}"),
+    };
+
+    notify("Submitting result...".to_string());
+    let submission = backend.submit(puzzle, part, &answer);
+
+    match &submission {
+        Ok(submission) => {
+            notify(submission.to_string());
+
+            let entry =
+                Entry::from_submission(puzzle.day, part, &answer, submission, ledger::now());
+            if let Some(wait_until) = entry.wait_until {
+                let wait = Duration::from_secs(wait_until.saturating_sub(entry.timestamp));
+                notify(format!("The next answer can be submitted in {wait:?}."));
+            }
+            if let Err(e) = ledger.record(entry) {
+                eprintln!("Could not record the submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(submission)
 }
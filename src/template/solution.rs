@@ -0,0 +1,61 @@
+/// Type-erased access to the solutions of all puzzles, so they can be run
+/// in-process instead of through their binaries.
+use crate::template::report::PartReport;
+use crate::PuzzleId;
+
+#[cfg(feature = "in_process")]
+use crate::solutions::SOLUTIONS;
+
+/// Without the `in_process` feature, no solutions are compiled into the library.
+#[cfg(not(feature = "in_process"))]
+static SOLUTIONS: &[&dyn Solution] = &[];
+
+/// The solution of a single puzzle. Implemented by the `solution!` macro.
+pub trait Solution: Sync {
+    fn puzzle(&self) -> PuzzleId;
+
+    fn part_one(&self, input: &str) -> Option<String>;
+
+    fn part_two(&self, input: &str) -> Option<String>;
+
+    /// Run both parts with the runner, printing their results like the solution binary does.
+    /// Solutions with a shared parse step report it before the parts.
+    fn run(&self, input: &str) -> Vec<PartReport>;
+}
+
+/// All registered solutions, ordered by year and day.
+#[must_use]
+pub fn all() -> &'static [&'static dyn Solution] {
+    SOLUTIONS
+}
+
+/// The registered solution for a puzzle, if it has been scaffolded.
+#[must_use]
+pub fn get(puzzle: PuzzleId) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().find(|s| s.puzzle() == puzzle).copied()
+}
+
+#[cfg(all(feature = "test_lib", feature = "in_process"))]
+mod tests {
+    use super::{all, get};
+    use crate::{all_days, day, template::read_file, year, PuzzleId};
+
+    #[test]
+    fn registers_puzzles_in_order() {
+        let puzzles: Vec<_> = all().iter().map(|s| s.puzzle()).collect();
+        let mut sorted = puzzles.clone();
+        sorted.sort();
+        assert_eq!(puzzles, sorted);
+        assert!(all_days()
+            .map(|d| PuzzleId::new(year!(2023), d))
+            .all(|p| get(p).map_or(true, |s| s.puzzle() == p)));
+    }
+
+    #[test]
+    fn runs_part_in_process() {
+        let puzzle = PuzzleId::new(year!(2023), day!(1));
+        let solution = get(puzzle).unwrap();
+        let result = solution.part_one(&read_file("examples", puzzle).unwrap());
+        assert_eq!(result, Some("142".into()));
+    }
+}
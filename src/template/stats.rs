@@ -0,0 +1,106 @@
+/// Summary statistics of the samples collected while benching a part.
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The 95th percentile, using the nearest-rank method.
+    pub p95: Duration,
+    /// The sample standard deviation, used as the spread of the timings.
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    /// Compute the statistics of a set of timings. Returns [`None`] if there are
+    /// no timings.
+    #[must_use]
+    pub fn from_samples(timings: &[Duration]) -> Option<Self> {
+        if timings.is_empty() {
+            return None;
+        }
+
+        let mut sorted = timings.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let p95_rank = (n * 95).div_ceil(100);
+        let p95 = sorted[p95_rank.max(1) - 1];
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        #[allow(clippy::cast_precision_loss)]
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let from_nanos = |nanos: f64| Duration::from_nanos(nanos.round() as u64);
+
+        Some(BenchStats {
+            samples: n as u128,
+            min: sorted[0],
+            median,
+            mean: from_nanos(mean),
+            p95,
+            std_dev: from_nanos(variance.sqrt()),
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::BenchStats;
+
+    fn from_nanos(nanos: &[u64]) -> BenchStats {
+        let timings: Vec<_> = nanos.iter().copied().map(Duration::from_nanos).collect();
+        BenchStats::from_samples(&timings).unwrap()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = from_nanos(&[40, 10, 30, 20, 50]);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        // sqrt(1000 / 4) = 15.8
+        assert_eq!(stats.std_dev, Duration::from_nanos(16));
+    }
+
+    #[test]
+    fn is_robust_against_outliers() {
+        let mut nanos = vec![100; 99];
+        nanos.push(1_000_000);
+        let stats = from_nanos(&nanos);
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert!(stats.mean > Duration::from_nanos(10_000));
+    }
+
+    #[test]
+    fn handles_few_samples() {
+        let stats = from_nanos(&[10, 20]);
+        assert_eq!(stats.median, Duration::from_nanos(15));
+        assert_eq!(stats.p95, Duration::from_nanos(20));
+
+        let stats = from_nanos(&[10]);
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+}
@@ -0,0 +1,390 @@
+/// Scoped timers that show where a part spends its time, for `--profile`.
+///
+/// A span is timed from its creation until it is dropped, e.g.
+///
+/// ```ignore
+/// let _span = advent_of_code::span!("heuristic");
+/// ```
+///
+/// Spans nest: a span that starts while another is open becomes its child.
+/// Spans with the same name and parent are merged, counting their calls.
+/// Recording is switched off outside of [`record`], so apart from a
+/// thread-local lookup, spans cost nothing when not profiling. Only the thread
+/// that runs the part records its spans.
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Separates the names in the path of a nested span, e.g. `search/expand`.
+const PATH_SEPARATOR: char = '/';
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static RECORDER: RefCell<Recorder> = const {
+        RefCell::new(Recorder {
+            nodes: vec![],
+            stack: vec![],
+        })
+    };
+}
+
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    duration: Duration,
+    calls: u64,
+}
+
+/// The spans of the current thread, in the order they were first entered.
+struct Recorder {
+    nodes: Vec<Node>,
+    /// The open spans, innermost last.
+    stack: Vec<usize>,
+}
+
+impl Recorder {
+    fn enter(&mut self, name: &'static str) {
+        let parent = self.stack.last().copied();
+        let index = match self
+            .nodes
+            .iter()
+            .position(|n| n.parent == parent && n.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.nodes.push(Node {
+                    name,
+                    parent,
+                    duration: Duration::ZERO,
+                    calls: 0,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        self.stack.push(index);
+    }
+
+    fn exit(&mut self, duration: Duration) {
+        if let Some(index) = self.stack.pop() {
+            let node = &mut self.nodes[index];
+            node.duration += duration;
+            node.calls += 1;
+        }
+    }
+
+    fn path(&self, mut index: usize) -> String {
+        let mut names = vec![self.nodes[index].name];
+        while let Some(parent) = self.nodes[index].parent {
+            names.push(self.nodes[parent].name);
+            index = parent;
+        }
+        names.reverse();
+        names.join(&PATH_SEPARATOR.to_string())
+    }
+
+    /// The recorded spans, with every span directly followed by its children.
+    fn profile(&self) -> Profile {
+        let mut spans = vec![];
+        let mut pending: Vec<usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, n)| n.parent.is_none())
+            .map(|(i, _)| i)
+            .collect();
+
+        while let Some(index) = pending.pop() {
+            let node = &self.nodes[index];
+            spans.push(SpanStats {
+                path: self.path(index),
+                duration: node.duration,
+                calls: node.calls,
+            });
+            pending.extend(
+                self.nodes
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .filter(|(_, n)| n.parent == Some(index))
+                    .map(|(i, _)| i),
+            );
+        }
+
+        Profile { spans }
+    }
+}
+
+/// A timer that records its duration when it is dropped. Create one with
+/// [`crate::span!`].
+#[must_use = "a span is timed until it is dropped"]
+pub struct Span {
+    start: Option<Instant>,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Self {
+        if !ENABLED.with(Cell::get) {
+            return Span { start: None };
+        }
+
+        RECORDER.with(|recorder| recorder.borrow_mut().enter(name));
+        Span {
+            start: Some(Instant::now()),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let duration = start.elapsed();
+            RECORDER.with(|recorder| recorder.borrow_mut().exit(duration));
+        }
+    }
+}
+
+/// Time the rest of the enclosing scope as a span with the given name, if the
+/// part is being profiled. Bind the span to a variable, e.g.
+/// `let _span = span!("search");`, as `_` would drop it right away.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::template::trace::Span::enter($name)
+    };
+}
+
+/// The total duration and number of calls of a span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanStats {
+    /// The names of the span and its parents, e.g. `search/expand`.
+    pub path: String,
+    pub duration: Duration,
+    pub calls: u64,
+}
+
+impl SpanStats {
+    /// The number of parents of the span.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.path.matches(PATH_SEPARATOR).count()
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.path
+            .rsplit(PATH_SEPARATOR)
+            .next()
+            .unwrap_or(&self.path)
+    }
+}
+
+/// The spans of a part, with every span directly followed by its children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub spans: Vec<SpanStats>,
+}
+
+impl Profile {
+    /// Add the spans of another profile, merging spans with the same path.
+    pub fn merge(&mut self, other: &Profile) {
+        for span in &other.spans {
+            match self.spans.iter_mut().find(|s| s.path == span.path) {
+                Some(existing) => {
+                    existing.duration += span.duration;
+                    existing.calls += span.calls;
+                }
+                None => {
+                    // keep children after their parent.
+                    let parent = span.path.rsplit_once(PATH_SEPARATOR).map(|(p, _)| p);
+                    let position = parent
+                        .and_then(|parent| {
+                            self.spans.iter().rposition(|s| {
+                                s.path == parent
+                                    || s.path.starts_with(&format!("{parent}{PATH_SEPARATOR}"))
+                            })
+                        })
+                        .map_or(self.spans.len(), |i| i + 1);
+                    self.spans.insert(position, span.clone());
+                }
+            }
+        }
+    }
+
+    /// The profile as a tree, one span per line.
+    #[must_use]
+    pub fn format_tree(&self, indent: &str) -> String {
+        let width = self
+            .spans
+            .iter()
+            .map(|s| 2 * s.depth() + s.name().chars().count())
+            .max()
+            .unwrap_or(0);
+
+        self.spans
+            .iter()
+            .map(|span| {
+                let name = format!("{}{}", "  ".repeat(span.depth()), span.name());
+                format!(
+                    "{indent}{name:<width$}  {:>10.1?}  ×{}",
+                    span.duration, span.calls
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The compact representation in the `profile` field of the JSON records:
+/// `path:duration_ns:calls` per span, separated by `;`.
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let spans: Vec<String> = self
+            .spans
+            .iter()
+            .map(|s| format!("{}:{}:{}", s.path, s.duration.as_nanos(), s.calls))
+            .collect();
+        write!(f, "{}", spans.join(";"))
+    }
+}
+
+/// An error which can be returned when parsing a [`Profile`].
+#[derive(Debug)]
+pub struct ProfileFromStrError;
+
+impl std::error::Error for ProfileFromStrError {}
+
+impl Display for ProfileFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting spans like `search:1200:1`, separated by `;`")
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ProfileFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Profile::default());
+        }
+
+        let spans = s
+            .split(';')
+            .map(|span| {
+                let mut fields = span.rsplitn(3, ':');
+                let calls = fields.next()?.parse().ok()?;
+                let duration = Duration::from_nanos(fields.next()?.parse().ok()?);
+                let path = fields.next().filter(|p| !p.is_empty())?;
+                Some(SpanStats {
+                    path: path.to_string(),
+                    duration,
+                    calls,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ProfileFromStrError)?;
+
+        Ok(Profile { spans })
+    }
+}
+
+/// Run a function and record the spans it enters on the current thread.
+/// Recordings can not be nested.
+pub fn record<T>(func: impl FnOnce() -> T) -> (T, Profile) {
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.nodes.clear();
+        recorder.stack.clear();
+    });
+    ENABLED.with(|enabled| enabled.set(true));
+
+    let result = func();
+
+    ENABLED.with(|enabled| enabled.set(false));
+    let profile = RECORDER.with(|recorder| recorder.borrow().profile());
+
+    (result, profile)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{record, Profile, SpanStats};
+
+    fn span(path: &str, nanos: u64, calls: u64) -> SpanStats {
+        SpanStats {
+            path: path.into(),
+            duration: Duration::from_nanos(nanos),
+            calls,
+        }
+    }
+
+    #[test]
+    fn records_nested_spans() {
+        let (sum, profile) = record(|| {
+            let _outer = crate::span!("search");
+            let mut sum = 0;
+            for i in 0..3 {
+                let _inner = crate::span!("expand");
+                sum += i;
+            }
+            let _heuristic = crate::span!("heuristic");
+            sum
+        });
+        assert_eq!(sum, 3);
+
+        let spans: Vec<(&str, u64)> = profile
+            .spans
+            .iter()
+            .map(|s| (s.path.as_str(), s.calls))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![("search", 1), ("search/expand", 3), ("search/heuristic", 1)]
+        );
+        assert_eq!(profile.spans[1].depth(), 1);
+        assert_eq!(profile.spans[1].name(), "expand");
+    }
+
+    #[test]
+    fn ignores_spans_when_not_recording() {
+        let _span = crate::span!("ignored");
+        let ((), profile) = record(|| {});
+        assert_eq!(profile, Profile::default());
+    }
+
+    #[test]
+    fn round_trips_strings() {
+        let profile = Profile {
+            spans: vec![span("parse", 1200, 1), span("parse/line", 1000, 140)],
+        };
+        assert_eq!(profile.to_string(), "parse:1200:1;parse/line:1000:140");
+        assert_eq!(profile.to_string().parse::<Profile>().unwrap(), profile);
+        assert_eq!("".parse::<Profile>().unwrap(), Profile::default());
+        assert!("parse:12".parse::<Profile>().is_err());
+    }
+
+    #[test]
+    fn merges_profiles() {
+        let mut profile = Profile {
+            spans: vec![span("a", 10, 1), span("a/b", 5, 2), span("c", 1, 1)],
+        };
+        profile.merge(&Profile {
+            spans: vec![span("a", 10, 1), span("a/d", 3, 1), span("e", 1, 1)],
+        });
+        assert_eq!(
+            profile.spans,
+            vec![
+                span("a", 20, 2),
+                span("a/b", 5, 2),
+                span("a/d", 3, 1),
+                span("c", 1, 1),
+                span("e", 1, 1)
+            ]
+        );
+    }
+}